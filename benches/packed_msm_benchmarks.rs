@@ -59,7 +59,11 @@ mod packed_msm_benches {
             );
 
             c.bench_function(&label, |b| {
-                b.iter(|| handle.packed_msm(&mut res, &output_bit_table, &scalars))
+                b.iter(|| {
+                    handle
+                        .packed_msm(&mut res, &output_bit_table, &scalars)
+                        .unwrap()
+                })
             });
         }
     }
@@ -0,0 +1,51 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate blitzar;
+extern crate curve25519_dalek;
+
+use blitzar::compute::*;
+use curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar};
+
+fn main() {
+    /////////////////////////////////////////////
+    // Commit to two columns
+    /////////////////////////////////////////////
+    let a: &[u64] = &[1, 2, 3];
+    let b: &[u64] = &[4, 5, 6];
+    let mut commitments = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments(&mut commitments, &[a.into(), b.into()], 0);
+
+    /////////////////////////////////////////////
+    // Derive the commitment of the column a + 2 * b
+    // without committing to it
+    /////////////////////////////////////////////
+    let derived = linear_combination_of_commitments(
+        &commitments,
+        &[Scalar::from(1_u64), Scalar::from(2_u64)],
+    )
+    .unwrap();
+
+    let c: Vec<u64> = a.iter().zip(b).map(|(a, b)| a + 2 * b).collect();
+    let mut expected = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut expected, &[(&c).into()], 0);
+    assert_eq!(derived, expected[0]);
+
+    /////////////////////////////////////////////
+    // Batches of commitments can also be added,
+    // subtracted, negated and scaled
+    /////////////////////////////////////////////
+    let sums = add_commitments(&commitments[..1], &commitments[1..]).unwrap();
+    let differences = sub_commitments(&sums, &commitments[1..]).unwrap();
+    assert_eq!(differences[0], commitments[0]);
+}
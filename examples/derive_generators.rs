@@ -0,0 +1,45 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate blitzar;
+extern crate curve25519_dalek;
+
+use blitzar::compute::*;
+use curve25519_dalek::ristretto::RistrettoPoint;
+
+fn main() {
+    /////////////////////////////////////////////
+    // The generators of the commitment computation can be
+    // derived in pure Rust, without the CPU/GPU code.
+    // We want the 3 generators from the [4, 6] index range
+    /////////////////////////////////////////////
+    let offset_generators = 4;
+    let mut derived = vec![RistrettoPoint::default(); 3];
+    derive_curve25519_generators(&mut derived, offset_generators);
+
+    /////////////////////////////////////////////
+    // Each generator only depends on its index
+    /////////////////////////////////////////////
+    assert_eq!(
+        derived[1],
+        derive_curve25519_generator(offset_generators + 1)
+    );
+
+    /////////////////////////////////////////////
+    // Compare the derived generators with the ones
+    // used by our CPU/GPU code
+    /////////////////////////////////////////////
+    let mut gs = vec![RistrettoPoint::default(); 3];
+    get_curve25519_generators(&mut gs, offset_generators);
+    assert_eq!(derived, gs);
+}
@@ -0,0 +1,49 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate blitzar;
+extern crate curve25519_dalek;
+
+use blitzar::compute::*;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+
+fn main() {
+    /////////////////////////////////////////////
+    // Each protocol derives its own generators from a label,
+    // so its commitments do not share bases with other protocols
+    /////////////////////////////////////////////
+    let data: &[u16] = &[99, 123, 456];
+    let mut gs = vec![RistrettoPoint::default(); data.len()];
+    derive_labeled_curve25519_generators(b"my protocol", &mut gs, 0);
+
+    /////////////////////////////////////////////
+    // Commit to the data with the labeled generators
+    /////////////////////////////////////////////
+    let mut commitments = vec![Default::default(); 1];
+    compute_curve25519_commitments_with_generators(&mut commitments, &[data.into()], &gs);
+
+    let expected_commit: RistrettoPoint = data
+        .iter()
+        .zip(&gs)
+        .map(|(d, g)| Scalar::from(*d) * g)
+        .sum();
+    assert_eq!(expected_commit.compress(), commitments[0]);
+
+    /////////////////////////////////////////////
+    // Other labels give different generators
+    /////////////////////////////////////////////
+    assert_ne!(
+        derive_labeled_curve25519_generator(b"other protocol", 0),
+        gs[0]
+    );
+}
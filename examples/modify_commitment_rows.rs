@@ -0,0 +1,68 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate blitzar;
+extern crate curve25519_dalek;
+
+use blitzar::compute::*;
+use curve25519_dalek::ristretto::CompressedRistretto;
+
+fn main() {
+    /////////////////////////////////////////////
+    // Commit to two columns
+    /////////////////////////////////////////////
+    let a: &[u32] = &[1, 2, 3, 4];
+    let b: &[u32] = &[5, 6, 7, 8];
+    let mut commitments = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments(&mut commitments, &[a.into(), b.into()], 0);
+
+    /////////////////////////////////////////////
+    // Overwrite rows 1 and 3 of both columns,
+    // passing the old and new values of those rows
+    /////////////////////////////////////////////
+    let rows = [1, 3];
+    let old_a: &[u32] = &[2, 4];
+    let old_b: &[u32] = &[6, 8];
+    let new_a: &[u32] = &[20, 40];
+    let new_b: &[u32] = &[60, 80];
+    modify_curve25519_commitment_rows(
+        &mut commitments,
+        &rows,
+        &[old_a.into(), old_b.into()],
+        &[new_a.into(), new_b.into()],
+        0,
+    )
+    .unwrap();
+
+    let mut expected = vec![CompressedRistretto::default(); 2];
+    let modified_a: &[u32] = &[1, 20, 3, 40];
+    let modified_b: &[u32] = &[5, 60, 7, 80];
+    compute_curve25519_commitments(&mut expected, &[modified_a.into(), modified_b.into()], 0);
+    assert_eq!(commitments, expected);
+
+    /////////////////////////////////////////////
+    // Delete the last row of both columns
+    /////////////////////////////////////////////
+    delete_curve25519_commitment_rows(
+        &mut commitments,
+        &[3],
+        &[new_a[1..].into(), new_b[1..].into()],
+        0,
+    )
+    .unwrap();
+
+    let shortened_a: &[u32] = &[1, 20, 3];
+    let shortened_b: &[u32] = &[5, 60, 7];
+    compute_curve25519_commitments(&mut expected, &[shortened_a.into(), shortened_b.into()], 0);
+    assert_eq!(commitments, expected);
+}
@@ -0,0 +1,64 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+extern crate blitzar;
+extern crate curve25519_dalek;
+
+use blitzar::compute::*;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use rand_core::OsRng;
+
+fn main() {
+    /////////////////////////////////////////////
+    // Create a handle from some random generators
+    /////////////////////////////////////////////
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..4).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    /////////////////////////////////////////////
+    // Define columns of different types and lengths
+    //
+    // Each column is the scalar vector of one MSM:
+    //  flags:  1 * g[0] + 0 * g[1] + 1 * g[2]
+    //  counts: 7 * g[0] + 300 * g[1] + 9 * g[2] + 12 * g[3]
+    //  deltas: -2 * g[0] + 5 * g[1]
+    /////////////////////////////////////////////
+    let flags = [true, false, true];
+    let counts: Vec<u16> = vec![7, 300, 9, 12];
+    let deltas: Vec<i32> = vec![-2, 5];
+
+    /////////////////////////////////////////////
+    // Pack the columns: the flags only use one bit per row
+    /////////////////////////////////////////////
+    let mut scalars = PackedScalars::new();
+    scalars
+        .push_bits(&flags)
+        .push((&counts).into())
+        .push((&deltas).into());
+
+    /////////////////////////////////////////////
+    // Do the actual MSM computation, where each column
+    // only uses as many generators as it has elements
+    /////////////////////////////////////////////
+    let mut res = vec![RistrettoPoint::default(); scalars.num_outputs()];
+    scalars.vlen_msm(&handle, &mut res).unwrap();
+
+    /////////////////////////////////////////////
+    // Print result
+    /////////////////////////////////////////////
+    for (i, resi) in res.iter().enumerate() {
+        println!("result {}: {:?}\n", i, resi);
+    }
+}
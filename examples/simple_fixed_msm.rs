@@ -57,7 +57,7 @@ fn main() {
     /////////////////////////////////////////////
     // Do the actual MSM computation
     /////////////////////////////////////////////
-    handle.msm(&mut res, 1, data);
+    handle.msm(&mut res, 1, data).unwrap();
 
     /////////////////////////////////////////////
     // Print result
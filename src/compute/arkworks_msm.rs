@@ -0,0 +1,120 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+    compute::{
+        compute_bls12_381_g1_commitments_with_generators,
+        compute_bn254_g1_uncompressed_commitments_with_generators,
+        compute_grumpkin_uncompressed_commitments_with_generators, curve::SwCurveConfig, ElementP2,
+        GrumpkinAffine, GrumpkinConfig, MsmError, MsmHandle,
+    },
+    sequence::Sequence,
+};
+use ark_ec::{
+    short_weierstrass::{Affine, Projective},
+    AffineRepr, VariableBaseMSM,
+};
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalDeserialize;
+use ark_std::Zero;
+use rayon::prelude::*;
+
+/// Multi-scalar multiplication computed by blitzar with the interface of
+/// arkworks' [VariableBaseMSM].
+///
+/// Call sites of `G::msm(bases, scalars)` can switch to `G::blitzar_msm(bases, scalars)`
+/// without changing how bases and scalars are laid out. It is implemented for
+/// the G1 projective groups of BLS12-381 and BN254 and for the Grumpkin projective
+/// group.
+///
+/// Each call computes a single commitment to `scalars` with `bases` as the
+/// generators, e.g. with [crate::compute::compute_bls12_381_g1_commitments_with_generators],
+/// so no [MsmHandle] is created. When the same bases are used repeatedly, create a
+/// handle once with [crate::compute::SwMsmHandle::new_with_affine] and use
+/// [MsmHandle::variable_base_msm] instead.
+pub trait BlitzarMsm: VariableBaseMSM {
+    /// Computes an MSM over the first `min(bases.len(), scalars.len())` elements.
+    fn blitzar_msm_unchecked(bases: &[Self::MulBase], scalars: &[Self::ScalarField]) -> Self;
+
+    /// Computes an MSM, returning the length of the shorter input as the error
+    /// if `bases` and `scalars` have different lengths, as [VariableBaseMSM::msm] does.
+    fn blitzar_msm(bases: &[Self::MulBase], scalars: &[Self::ScalarField]) -> Result<Self, usize> {
+        if bases.len() == scalars.len() {
+            Ok(Self::blitzar_msm_unchecked(bases, scalars))
+        } else {
+            Err(bases.len().min(scalars.len()))
+        }
+    }
+}
+
+// Implements `BlitzarMsm` for the projective group of a curve with its commitment
+// function, starting from the commitment `$commitment` and converting the result
+// to an affine point with `$to_affine`.
+macro_rules! impl_blitzar_msm {
+    ($config:ty, $commitment:expr, $compute:ident, $to_affine:expr) => {
+        impl BlitzarMsm for Projective<$config> {
+            fn blitzar_msm_unchecked(
+                bases: &[Affine<$config>],
+                scalars: &[<$config as ark_ec::CurveConfig>::ScalarField],
+            ) -> Self {
+                let n = bases.len().min(scalars.len());
+                if n == 0 {
+                    return Self::zero();
+                }
+                let scalars: Vec<_> = scalars[..n]
+                    .par_iter()
+                    .map(|scalar| scalar.into_bigint())
+                    .collect();
+                let mut commitment = [$commitment];
+                $compute(&mut commitment, &[Sequence::from(&scalars)], &bases[..n]);
+                let [commitment] = commitment;
+                ($to_affine)(commitment).into_group()
+            }
+        }
+    };
+}
+
+impl_blitzar_msm!(
+    ark_bls12_381::g1::Config,
+    [0_u8; 48],
+    compute_bls12_381_g1_commitments_with_generators,
+    |bytes: [u8; 48]| {
+        ark_bls12_381::G1Affine::deserialize_compressed(&bytes[..])
+            .expect("the backend returns valid commitments")
+    }
+);
+impl_blitzar_msm!(
+    ark_bn254::g1::Config,
+    ark_bn254::G1Affine::identity(),
+    compute_bn254_g1_uncompressed_commitments_with_generators,
+    |point: ark_bn254::G1Affine| point
+);
+impl_blitzar_msm!(
+    GrumpkinConfig,
+    GrumpkinAffine::identity(),
+    compute_grumpkin_uncompressed_commitments_with_generators,
+    |point: GrumpkinAffine| point
+);
+
+impl<C: SwCurveConfig + Clone> MsmHandle<ElementP2<C>> {
+    /// Computes the MSM of `scalars` with the first `scalars.len()` generators
+    /// of the handle, returning an arkworks projective point.
+    ///
+    /// Returns an error if there are more scalars than generators.
+    pub fn variable_base_msm(&self, scalars: &[C::ScalarField]) -> Result<Projective<C>, MsmError> {
+        let mut res = [ElementP2::<C>::default()];
+        self.msm_with_field_elements(&mut res, scalars)?;
+        let [res] = res;
+        Ok(Affine::<C>::from(res).into_group())
+    }
+}
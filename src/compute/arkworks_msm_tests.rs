@@ -0,0 +1,65 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use ark_ec::{
+    short_weierstrass::{Affine, Projective},
+    CurveGroup, VariableBaseMSM,
+};
+use ark_std::{UniformRand, Zero};
+
+fn we_can_compute_msms_with_the_arkworks_interface<C: SwCurveConfig + Clone>()
+where
+    Projective<C>: BlitzarMsm<MulBase = Affine<C>, ScalarField = C::ScalarField>,
+{
+    let mut rng = ark_std::test_rng();
+    let bases: Vec<_> = (0..5)
+        .map(|_| Projective::<C>::rand(&mut rng).into_affine())
+        .collect();
+    let scalars: Vec<C::ScalarField> = (0..5).map(|_| C::ScalarField::rand(&mut rng)).collect();
+
+    let expected = Projective::<C>::msm(&bases, &scalars).unwrap();
+    assert_eq!(
+        Projective::<C>::blitzar_msm(&bases, &scalars).unwrap(),
+        expected
+    );
+
+    // the handle based path agrees with the one-shot path
+    let handle = MsmHandle::<ElementP2<C>>::new_with_affine(&bases);
+    assert_eq!(handle.variable_base_msm(&scalars).unwrap(), expected);
+
+    // mismatched lengths are reported like arkworks does
+    assert_eq!(Projective::<C>::blitzar_msm(&bases, &scalars[..3]), Err(3));
+    assert_eq!(
+        Projective::<C>::blitzar_msm_unchecked(&bases, &scalars[..3]),
+        Projective::<C>::msm_unchecked(&bases[..3], &scalars[..3])
+    );
+
+    // empty MSMs are the identity
+    assert!(Projective::<C>::blitzar_msm(&[], &[]).unwrap().is_zero());
+}
+
+#[test]
+fn we_can_compute_bls12_381_g1_msms_with_the_arkworks_interface() {
+    we_can_compute_msms_with_the_arkworks_interface::<ark_bls12_381::g1::Config>();
+}
+
+#[test]
+fn we_can_compute_bn254_g1_msms_with_the_arkworks_interface() {
+    we_can_compute_msms_with_the_arkworks_interface::<ark_bn254::g1::Config>();
+}
+
+#[test]
+fn we_can_compute_grumpkin_msms_with_the_arkworks_interface() {
+    we_can_compute_msms_with_the_arkworks_interface::<GrumpkinConfig>();
+}
@@ -11,6 +11,8 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+#[cfg(feature = "reference")]
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Once;
 
 /// Struct to hold configuration values about the chosen backend.
@@ -20,12 +22,22 @@ pub struct BackendConfig {
 }
 
 // holds the state of the backend initialization (0 for success, non-zero otherwise)
+#[cfg(feature = "blitzar-sys")]
 static mut INIT_STATE: i32 = 0;
 
 // static variable used to assure that the backend initialization is triggered only once
 static INIT: Once = Once::new();
 
+// set when the backend was initialized with the pure-Rust reference implementation
+#[cfg(feature = "reference")]
+static USE_REFERENCE: AtomicBool = AtomicBool::new(false);
+
+/// Environment variable that selects the reference backend when set to `reference`.
+#[cfg(feature = "reference")]
+const BACKEND_ENV_VAR: &str = "BLITZAR_BACKEND";
+
 /// verify which feature backend was passed to the build
+#[cfg(feature = "blitzar-sys")]
 fn get_backend() -> i32 {
     if cfg!(feature = "cpu") {
         blitzar_sys::SXT_CPU_BACKEND as i32
@@ -36,6 +48,49 @@ fn get_backend() -> i32 {
     }
 }
 
+// initializes the backend once, with the reference implementation if `reference` is set,
+// if it is selected by the environment, or if blitzar-sys is not available
+#[cfg_attr(not(feature = "blitzar-sys"), allow(unused_variables))]
+fn init(num_precomputed_generators: u64, reference: bool) {
+    INIT.call_once(|| {
+        #[cfg(feature = "reference")]
+        let reference = reference
+            || !cfg!(feature = "blitzar-sys")
+            || std::env::var(BACKEND_ENV_VAR).is_ok_and(|v| v == "reference");
+        #[cfg(feature = "reference")]
+        USE_REFERENCE.store(reference, Ordering::Relaxed);
+
+        #[cfg(feature = "blitzar-sys")]
+        if !reference {
+            let backend = get_backend();
+
+            // initializes the backend using the lower-level rust sys crate
+            let config: blitzar_sys::sxt_config = blitzar_sys::sxt_config {
+                backend,
+                num_precomputed_generators,
+            };
+
+            unsafe {
+                INIT_STATE = blitzar_sys::sxt_init(&config);
+            }
+        }
+    });
+
+    #[cfg(feature = "blitzar-sys")]
+    if unsafe { INIT_STATE } != 0 {
+        panic!("Error during backend initialization");
+    }
+}
+
+/// Initializes the backend and returns whether it uses the reference implementation.
+pub(crate) fn use_reference() -> bool {
+    init_backend();
+    #[cfg(feature = "reference")]
+    return USE_REFERENCE.load(Ordering::Relaxed);
+    #[cfg(not(feature = "reference"))]
+    false
+}
+
 #[doc = include_str!("../../docs/commitments/init_backend.md")]
 ///
 /// # Example - Initializing the Backend
@@ -48,25 +103,7 @@ fn get_backend() -> i32 {
 #[doc = include_str!("../../examples/initialize_backend.rs")]
 /// ```
 pub fn init_backend() {
-    unsafe {
-        let num_precomputed_generators: u64 = 20;
-
-        INIT.call_once(|| {
-            let backend = get_backend();
-
-            // initializes the backend using the lower-level rust sys crate
-            let config: blitzar_sys::sxt_config = blitzar_sys::sxt_config {
-                backend,
-                num_precomputed_generators,
-            };
-
-            INIT_STATE = blitzar_sys::sxt_init(&config);
-        });
-
-        if INIT_STATE != 0 {
-            panic!("Error during backend initialization");
-        }
-    };
+    init(20, false);
 }
 
 #[doc = include_str!("../../docs/commitments/init_backend_with_config.md")]
@@ -84,21 +121,23 @@ pub fn init_backend() {
 #[doc = include_str!("../../examples/initialize_backend_with_config.rs")]
 /// ```
 pub fn init_backend_with_config(config: BackendConfig) {
-    unsafe {
-        INIT.call_once(|| {
-            let backend = get_backend();
-
-            // initializes the backend using the lower-level rust sys crate
-            let config: blitzar_sys::sxt_config = blitzar_sys::sxt_config {
-                backend,
-                num_precomputed_generators: config.num_precomputed_generators,
-            };
-
-            INIT_STATE = blitzar_sys::sxt_init(&config);
-        });
+    init(config.num_precomputed_generators, false);
+}
 
-        if INIT_STATE != 0 {
-            panic!("Error during backend initialization");
-        }
-    };
+/// Initializes the backend with the pure-Rust reference implementation.
+///
+/// Afterwards, commitments, generators, [crate::compute::MsmHandle] and
+/// [crate::proof::InnerProductProof] are computed by [crate::compute::reference]
+/// instead of blitzar-sys. Setting the `BLITZAR_BACKEND` environment variable to
+/// `reference` has the same effect on the first initialization of the backend.
+///
+/// # Panics
+///
+/// If the backend was already initialized with the native implementation.
+#[cfg(feature = "reference")]
+pub fn init_reference_backend() {
+    init(0, true);
+    if !use_reference() {
+        panic!("The backend was already initialized with the native implementation");
+    }
 }
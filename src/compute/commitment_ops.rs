@@ -0,0 +1,204 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::{CommitmentError, CompressedBls12381G1Commitment, CompressedBn254G1Commitment};
+use ark_ec::{short_weierstrass::Projective, CurveGroup, VariableBaseMSM};
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::VartimeMultiscalarMul,
+};
+use rayon::prelude::*;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Compressed commitment supported by the commitment arithmetic of this module.
+pub trait Commitment: Copy + Send + Sync {
+    /// Decompressed point of the commitment.
+    type Point: Copy
+        + Send
+        + Sync
+        + Add<Output = Self::Point>
+        + Sub<Output = Self::Point>
+        + Neg<Output = Self::Point>
+        + Mul<Self::Scalar, Output = Self::Point>;
+
+    /// Scalar field of the commitment's group.
+    type Scalar: Copy + Send + Sync;
+
+    /// Decompresses the commitment, or returns `None` if it is not a valid point.
+    fn to_point(&self) -> Option<Self::Point>;
+
+    /// Compresses a batch of points.
+    fn from_points(points: &[Self::Point]) -> Vec<Self>;
+
+    /// Computes the sum of `scalars[i] * points[i]`.
+    fn linear_combination(points: &[Self::Point], scalars: &[Self::Scalar]) -> Self::Point;
+}
+
+impl Commitment for CompressedRistretto {
+    type Point = RistrettoPoint;
+    type Scalar = Scalar;
+
+    fn to_point(&self) -> Option<RistrettoPoint> {
+        self.decompress()
+    }
+
+    fn from_points(points: &[RistrettoPoint]) -> Vec<Self> {
+        points.par_iter().map(RistrettoPoint::compress).collect()
+    }
+
+    fn linear_combination(points: &[RistrettoPoint], scalars: &[Scalar]) -> RistrettoPoint {
+        RistrettoPoint::vartime_multiscalar_mul(scalars, points)
+    }
+}
+
+macro_rules! impl_commitment {
+    ($name:ty, $config:ty, $scalar:ty) => {
+        impl Commitment for $name {
+            type Point = Projective<$config>;
+            type Scalar = $scalar;
+
+            fn to_point(&self) -> Option<Self::Point> {
+                Some(self.decompress().into())
+            }
+
+            fn from_points(points: &[Self::Point]) -> Vec<Self> {
+                Projective::normalize_batch(points)
+                    .par_iter()
+                    .map(Self::from)
+                    .collect()
+            }
+
+            fn linear_combination(points: &[Self::Point], scalars: &[$scalar]) -> Self::Point {
+                Projective::msm(&Projective::normalize_batch(points), scalars)
+                    .expect("points and scalars have the same length")
+            }
+        }
+    };
+}
+
+impl_commitment!(
+    CompressedBls12381G1Commitment,
+    ark_bls12_381::g1::Config,
+    ark_bls12_381::Fr
+);
+impl_commitment!(
+    CompressedBn254G1Commitment,
+    ark_bn254::g1::Config,
+    ark_bn254::Fr
+);
+
+// Decompresses every commitment of the input batch `operand`, reporting the index
+// of the first invalid one.
+fn to_points<C: Commitment>(
+    commitments: &[C],
+    operand: usize,
+) -> Result<Vec<C::Point>, CommitmentError> {
+    let points: Vec<Option<C::Point>> = commitments.par_iter().map(C::to_point).collect();
+    match points.iter().position(Option::is_none) {
+        Some(index) => Err(CommitmentError::InvalidCommitment { operand, index }),
+        None => Ok(points.into_iter().flatten().collect()),
+    }
+}
+
+fn check_lengths(expected: usize, found: usize) -> Result<(), CommitmentError> {
+    if expected != found {
+        return Err(CommitmentError::LengthMismatch { expected, found });
+    }
+    Ok(())
+}
+
+// Applies `op` to the pairs of points of `lhs` and `rhs`.
+fn zip_with<C: Commitment>(
+    lhs: &[C],
+    rhs: &[C],
+    op: impl Fn(C::Point, C::Point) -> C::Point + Sync,
+) -> Result<Vec<C>, CommitmentError> {
+    check_lengths(lhs.len(), rhs.len())?;
+    let lhs = to_points(lhs, 0)?;
+    let rhs = to_points(rhs, 1)?;
+    let points: Vec<C::Point> = lhs.par_iter().zip(&rhs).map(|(l, r)| op(*l, *r)).collect();
+    Ok(C::from_points(&points))
+}
+
+/// Adds two batches of commitments element-wise.
+///
+/// The result commits to the sum of the committed columns.
+///
+/// Returns an error if the batches have different lengths or a commitment is not a
+/// valid point. [CommitmentError::InvalidCommitment] holds the batch of the invalid
+/// commitment, 0 for `lhs` and 1 for `rhs`, and its index within that batch. `lhs`
+/// is checked first, and the first invalid commitment of a batch is reported.
+///
+/// # Example - Commitment of a Derived Column
+#[cfg_attr(any(feature = "blitzar-sys", feature = "reference"), doc = "```no_run")]
+#[cfg_attr(
+    not(any(feature = "blitzar-sys", feature = "reference")),
+    doc = "```ignore"
+)]
+#[doc = include_str!("../../examples/commitment_ops.rs")]
+///```
+pub fn add_commitments<C: Commitment>(lhs: &[C], rhs: &[C]) -> Result<Vec<C>, CommitmentError> {
+    zip_with(lhs, rhs, |l, r| l + r)
+}
+
+/// Subtracts two batches of commitments element-wise.
+///
+/// The result commits to the difference of the committed columns. See
+/// [add_commitments] for the errors.
+pub fn sub_commitments<C: Commitment>(lhs: &[C], rhs: &[C]) -> Result<Vec<C>, CommitmentError> {
+    zip_with(lhs, rhs, |l, r| l - r)
+}
+
+/// Negates a batch of commitments.
+///
+/// Returns an error if a commitment is not a valid point.
+pub fn neg_commitments<C: Commitment>(commitments: &[C]) -> Result<Vec<C>, CommitmentError> {
+    let points: Vec<C::Point> = to_points(commitments, 0)?
+        .into_par_iter()
+        .map(|p| -p)
+        .collect();
+    Ok(C::from_points(&points))
+}
+
+/// Multiplies each commitment by the scalar at the same index.
+///
+/// The result commits to the scaled columns. Returns an error if `scalars` does not
+/// have one entry per commitment or a commitment is not a valid point.
+pub fn scale_commitments<C: Commitment>(
+    commitments: &[C],
+    scalars: &[C::Scalar],
+) -> Result<Vec<C>, CommitmentError> {
+    check_lengths(commitments.len(), scalars.len())?;
+    let points: Vec<C::Point> = to_points(commitments, 0)?
+        .into_par_iter()
+        .zip(scalars)
+        .map(|(p, s)| p * *s)
+        .collect();
+    Ok(C::from_points(&points))
+}
+
+/// Computes the linear combination `sum(scalars[i] * commitments[i])`.
+///
+/// The result commits to the same linear combination of the committed columns.
+/// Returns an error if `scalars` does not have one entry per commitment or a
+/// commitment is not a valid point.
+pub fn linear_combination_of_commitments<C: Commitment>(
+    commitments: &[C],
+    scalars: &[C::Scalar],
+) -> Result<C, CommitmentError> {
+    check_lengths(commitments.len(), scalars.len())?;
+    let points = to_points(commitments, 0)?;
+    let point = C::linear_combination(&points, scalars);
+    Ok(C::from_points(&[point])[0])
+}
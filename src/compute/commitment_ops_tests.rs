@@ -0,0 +1,198 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use ark_ec::CurveGroup;
+use ark_std::UniformRand;
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+use rand_core::OsRng;
+
+fn random_ristretto_commitments(n: usize) -> (Vec<RistrettoPoint>, Vec<CompressedRistretto>) {
+    let points: Vec<RistrettoPoint> = (0..n).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let commitments = points.iter().map(RistrettoPoint::compress).collect();
+    (points, commitments)
+}
+
+#[test]
+fn we_can_add_and_subtract_ristretto_commitments() {
+    let (p, c) = random_ristretto_commitments(4);
+    let (q, d) = random_ristretto_commitments(4);
+
+    let sums = add_commitments(&c, &d).unwrap();
+    let differences = sub_commitments(&c, &d).unwrap();
+    for i in 0..4 {
+        assert_eq!(sums[i], (p[i] + q[i]).compress());
+        assert_eq!(differences[i], (p[i] - q[i]).compress());
+    }
+    assert_eq!(sub_commitments(&sums, &d).unwrap(), c);
+}
+
+#[test]
+fn we_can_negate_and_scale_ristretto_commitments() {
+    let (p, c) = random_ristretto_commitments(3);
+    let scalars: Vec<Scalar> = (0..3).map(|_| Scalar::random(&mut OsRng)).collect();
+
+    let negated = neg_commitments(&c).unwrap();
+    let scaled = scale_commitments(&c, &scalars).unwrap();
+    for i in 0..3 {
+        assert_eq!(negated[i], (-p[i]).compress());
+        assert_eq!(scaled[i], (p[i] * scalars[i]).compress());
+    }
+
+    let combination = linear_combination_of_commitments(&c, &scalars).unwrap();
+    let expected: RistrettoPoint = p.iter().zip(&scalars).map(|(p, s)| p * s).sum();
+    assert_eq!(combination, expected.compress());
+}
+
+#[test]
+fn commitment_ops_on_empty_batches_return_empty_batches() {
+    let empty: Vec<CompressedRistretto> = Vec::new();
+    assert!(add_commitments(&empty, &empty).unwrap().is_empty());
+    assert!(neg_commitments(&empty).unwrap().is_empty());
+    assert_eq!(
+        linear_combination_of_commitments(&empty, &[]).unwrap(),
+        RistrettoPoint::default().compress()
+    );
+}
+
+#[test]
+fn commitment_ops_report_invalid_commitments() {
+    let (_, mut c) = random_ristretto_commitments(3);
+    let (_, d) = random_ristretto_commitments(3);
+    c[1] = CompressedRistretto([0xff; 32]);
+
+    assert_eq!(
+        add_commitments(&d, &c),
+        Err(CommitmentError::InvalidCommitment {
+            operand: 1,
+            index: 1
+        })
+    );
+    assert_eq!(
+        neg_commitments(&c),
+        Err(CommitmentError::InvalidCommitment {
+            operand: 0,
+            index: 1
+        })
+    );
+    assert_eq!(
+        scale_commitments(&c, &[Scalar::ONE; 3]),
+        Err(CommitmentError::InvalidCommitment {
+            operand: 0,
+            index: 1
+        })
+    );
+}
+
+#[test]
+fn commitment_ops_report_the_first_invalid_commitment_of_lhs_first() {
+    let (_, mut c) = random_ristretto_commitments(4);
+    let (_, mut d) = random_ristretto_commitments(4);
+    c[0] = CompressedRistretto([0xff; 32]);
+    d[1] = CompressedRistretto([0xff; 32]);
+    d[3] = CompressedRistretto([0xff; 32]);
+
+    assert_eq!(
+        sub_commitments(&d, &c),
+        Err(CommitmentError::InvalidCommitment {
+            operand: 0,
+            index: 1
+        })
+    );
+    assert_eq!(
+        sub_commitments(&c, &d),
+        Err(CommitmentError::InvalidCommitment {
+            operand: 0,
+            index: 0
+        })
+    );
+}
+
+#[test]
+fn commitment_ops_report_length_mismatches() {
+    let (_, c) = random_ristretto_commitments(3);
+    assert_eq!(
+        add_commitments(&c, &c[..2]),
+        Err(CommitmentError::LengthMismatch {
+            expected: 3,
+            found: 2
+        })
+    );
+    assert_eq!(
+        scale_commitments(&c, &[Scalar::ONE; 4]),
+        Err(CommitmentError::LengthMismatch {
+            expected: 3,
+            found: 4
+        })
+    );
+    assert!(linear_combination_of_commitments(&c, &[]).is_err());
+}
+
+#[test]
+fn we_can_compute_arithmetic_on_bls12_381_commitments() {
+    let mut rng = ark_std::test_rng();
+    let p: Vec<ark_bls12_381::G1Projective> = (0..3)
+        .map(|_| ark_bls12_381::G1Projective::rand(&mut rng))
+        .collect();
+    let q: Vec<ark_bls12_381::G1Projective> = (0..3)
+        .map(|_| ark_bls12_381::G1Projective::rand(&mut rng))
+        .collect();
+    let scalars: Vec<ark_bls12_381::Fr> =
+        (0..3).map(|_| ark_bls12_381::Fr::rand(&mut rng)).collect();
+    let compress = |points: &[ark_bls12_381::G1Projective]| -> Vec<CompressedBls12381G1Commitment> {
+        points.iter().map(|p| p.into_affine().into()).collect()
+    };
+    let (c, d) = (compress(&p), compress(&q));
+
+    let sums: Vec<_> = p.iter().zip(&q).map(|(p, q)| p + q).collect();
+    assert_eq!(add_commitments(&c, &d).unwrap(), compress(&sums));
+    let differences: Vec<_> = p.iter().zip(&q).map(|(p, q)| p - q).collect();
+    assert_eq!(sub_commitments(&c, &d).unwrap(), compress(&differences));
+    let negated: Vec<_> = p.iter().map(|p| -*p).collect();
+    assert_eq!(neg_commitments(&c).unwrap(), compress(&negated));
+    let scaled: Vec<_> = p.iter().zip(&scalars).map(|(p, s)| *p * s).collect();
+    assert_eq!(scale_commitments(&c, &scalars).unwrap(), compress(&scaled));
+    let combination: ark_bls12_381::G1Projective = scaled.iter().sum();
+    assert_eq!(
+        linear_combination_of_commitments(&c, &scalars).unwrap(),
+        compress(&[combination])[0]
+    );
+}
+
+#[test]
+fn we_can_compute_arithmetic_on_bn254_commitments() {
+    let mut rng = ark_std::test_rng();
+    let p: Vec<ark_bn254::G1Projective> = (0..3)
+        .map(|_| ark_bn254::G1Projective::rand(&mut rng))
+        .collect();
+    let scalars: Vec<ark_bn254::Fr> = (0..3).map(|_| ark_bn254::Fr::rand(&mut rng)).collect();
+    let compress = |points: &[ark_bn254::G1Projective]| -> Vec<CompressedBn254G1Commitment> {
+        points.iter().map(|p| p.into_affine().into()).collect()
+    };
+    let c = compress(&p);
+
+    let doubled: Vec<_> = p.iter().map(|p| p + p).collect();
+    assert_eq!(add_commitments(&c, &c).unwrap(), compress(&doubled));
+    let zeros = vec![ark_bn254::G1Projective::default(); 3];
+    assert_eq!(sub_commitments(&c, &c).unwrap(), compress(&zeros));
+    let scaled: Vec<_> = p.iter().zip(&scalars).map(|(p, s)| *p * s).collect();
+    assert_eq!(scale_commitments(&c, &scalars).unwrap(), compress(&scaled));
+    let combination: ark_bn254::G1Projective = scaled.iter().sum();
+    assert_eq!(
+        linear_combination_of_commitments(&c, &scalars).unwrap(),
+        compress(&[combination])[0]
+    );
+}
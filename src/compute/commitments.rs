@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::backend::init_backend;
+#[cfg(feature = "blitzar-sys")]
+use super::backend::use_reference;
+use super::{reference, GrumpkinAffine};
 use crate::sequence::Sequence;
 use ark_bls12_381::G1Affine;
 use ark_bn254::G1Affine as bn254_g1_affine;
 use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use rayon::prelude::*;
 
 #[doc = include_str!("../../docs/commitments/compute_curve25519_commitments.md")]
 ///
@@ -39,22 +42,26 @@ pub fn compute_curve25519_commitments(
     data: &[Sequence],
     offset_generators: u64,
 ) {
-    init_backend();
+    #[cfg(feature = "blitzar-sys")]
+    if !use_reference() {
+        let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> =
+            data.iter().map(Into::into).collect();
 
-    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> =
-        data.iter().map(Into::into).collect();
+        let sxt_ristretto255_compressed =
+            commitments.as_mut_ptr() as *mut blitzar_sys::sxt_ristretto255_compressed;
 
-    let sxt_ristretto255_compressed =
-        commitments.as_mut_ptr() as *mut blitzar_sys::sxt_ristretto255_compressed;
-
-    unsafe {
-        blitzar_sys::sxt_curve25519_compute_pedersen_commitments(
-            sxt_ristretto255_compressed,
-            sxt_descriptors.len() as u32,
-            sxt_descriptors.as_ptr(),
-            offset_generators,
-        );
+        unsafe {
+            blitzar_sys::sxt_curve25519_compute_pedersen_commitments(
+                sxt_ristretto255_compressed,
+                sxt_descriptors.len() as u32,
+                sxt_descriptors.as_ptr(),
+                offset_generators,
+            );
+        }
+        return;
     }
+
+    reference::compute_curve25519_commitments(commitments, data, offset_generators);
 }
 
 #[doc = include_str!("../../docs/commitments/compute_curve25519_commitments_with_generators.md")]
@@ -73,32 +80,36 @@ pub fn compute_curve25519_commitments_with_generators(
     data: &[Sequence],
     generators: &[RistrettoPoint],
 ) {
-    init_backend();
-
-    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
-        .iter()
-        .map(|s| {
-            assert!(
-                s.len() <= generators.len(),
-                "generators has a length smaller than the longest sequence in the input data"
+    #[cfg(feature = "blitzar-sys")]
+    if !use_reference() {
+        let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
+            .iter()
+            .map(|s| {
+                assert!(
+                    s.len() <= generators.len(),
+                    "generators has a length smaller than the longest sequence in the input data"
+                );
+                s.into()
+            })
+            .collect();
+
+        let sxt_ristretto_generators = generators.as_ptr() as *const blitzar_sys::sxt_ristretto255;
+
+        let sxt_ristretto255_compressed =
+            commitments.as_mut_ptr() as *mut blitzar_sys::sxt_ristretto255_compressed;
+
+        unsafe {
+            blitzar_sys::sxt_curve25519_compute_pedersen_commitments_with_generators(
+                sxt_ristretto255_compressed,
+                sxt_descriptors.len() as u32,
+                sxt_descriptors.as_ptr(),
+                sxt_ristretto_generators,
             );
-            s.into()
-        })
-        .collect();
-
-    let sxt_ristretto_generators = generators.as_ptr() as *const blitzar_sys::sxt_ristretto255;
-
-    let sxt_ristretto255_compressed =
-        commitments.as_mut_ptr() as *mut blitzar_sys::sxt_ristretto255_compressed;
-
-    unsafe {
-        blitzar_sys::sxt_curve25519_compute_pedersen_commitments_with_generators(
-            sxt_ristretto255_compressed,
-            sxt_descriptors.len() as u32,
-            sxt_descriptors.as_ptr(),
-            sxt_ristretto_generators,
-        );
+        }
+        return;
     }
+
+    reference::compute_curve25519_commitments_with_generators(commitments, data, generators);
 }
 
 #[doc = include_str!("../../docs/commitments/compute_bls12_381_g1_commitments_with_generators.md")]
@@ -112,32 +123,37 @@ pub fn compute_bls12_381_g1_commitments_with_generators(
     data: &[Sequence],
     generators: &[G1Affine],
 ) {
-    init_backend();
-
-    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
-        .iter()
-        .map(|s| {
-            assert!(
-                s.len() <= generators.len(),
-                "generators has a length smaller than the longest sequence in the input data"
+    #[cfg(feature = "blitzar-sys")]
+    if !use_reference() {
+        let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
+            .iter()
+            .map(|s| {
+                assert!(
+                    s.len() <= generators.len(),
+                    "generators has a length smaller than the longest sequence in the input data"
+                );
+                s.into()
+            })
+            .collect();
+
+        let sxt_bls12_381_g1_generators =
+            generators.as_ptr() as *const blitzar_sys::sxt_bls12_381_g1;
+
+        let sxt_bls12_381_g1_compressed =
+            commitments.as_mut_ptr() as *mut blitzar_sys::sxt_bls12_381_g1_compressed;
+
+        unsafe {
+            blitzar_sys::sxt_bls12_381_g1_compute_pedersen_commitments_with_generators(
+                sxt_bls12_381_g1_compressed,
+                sxt_descriptors.len() as u32,
+                sxt_descriptors.as_ptr(),
+                sxt_bls12_381_g1_generators,
             );
-            s.into()
-        })
-        .collect();
-
-    let sxt_bls12_381_g1_generators = generators.as_ptr() as *const blitzar_sys::sxt_bls12_381_g1;
-
-    let sxt_bls12_381_g1_compressed =
-        commitments.as_mut_ptr() as *mut blitzar_sys::sxt_bls12_381_g1_compressed;
-
-    unsafe {
-        blitzar_sys::sxt_bls12_381_g1_compute_pedersen_commitments_with_generators(
-            sxt_bls12_381_g1_compressed,
-            sxt_descriptors.len() as u32,
-            sxt_descriptors.as_ptr(),
-            sxt_bls12_381_g1_generators,
-        );
+        }
+        return;
     }
+
+    reference::compute_bls12_381_g1_commitments_with_generators(commitments, data, generators);
 }
 
 #[doc = include_str!("../../docs/commitments/compute_bn254_g1_commitments_with_generators.md")]
@@ -151,31 +167,85 @@ pub fn compute_bn254_g1_uncompressed_commitments_with_generators(
     data: &[Sequence],
     generators: &[bn254_g1_affine],
 ) {
-    init_backend();
-
-    let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
-        .iter()
-        .map(|s| {
-            assert!(
-                s.len() <= generators.len(),
-                "generators has a length smaller than the longest sequence in the input data"
+    #[cfg(feature = "blitzar-sys")]
+    if !use_reference() {
+        let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
+            .iter()
+            .map(|s| {
+                assert!(
+                    s.len() <= generators.len(),
+                    "generators has a length smaller than the longest sequence in the input data"
+                );
+                s.into()
+            })
+            .collect();
+
+        let sxt_bn254_g1_generators = generators.as_ptr() as *const blitzar_sys::sxt_bn254_g1;
+
+        let sxt_bn254_g1_uncompressed = commitments.as_mut_ptr() as *mut blitzar_sys::sxt_bn254_g1;
+
+        unsafe {
+            blitzar_sys::sxt_bn254_g1_uncompressed_compute_pedersen_commitments_with_generators(
+                sxt_bn254_g1_uncompressed,
+                sxt_descriptors.len() as u32,
+                sxt_descriptors.as_ptr(),
+                sxt_bn254_g1_generators,
             );
-            s.into()
-        })
-        .collect();
+        }
+        return;
+    }
+
+    reference::compute_bn254_g1_uncompressed_commitments_with_generators(
+        commitments,
+        data,
+        generators,
+    );
+}
+
+#[doc = include_str!("../../docs/commitments/compute_grumpkin_uncompressed_commitments_with_generators.md")]
+///
+/// # Example - Pass generators to Commitment Computation
+///```no_run
+#[doc = include_str!("../../examples/pass_grumpkin_generators_to_commitment.rs")]
+///```
+pub fn compute_grumpkin_uncompressed_commitments_with_generators(
+    commitments: &mut [GrumpkinAffine],
+    data: &[Sequence],
+    generators: &[GrumpkinAffine],
+) {
+    #[cfg(feature = "blitzar-sys")]
+    if !use_reference() {
+        let sxt_descriptors: Vec<blitzar_sys::sxt_sequence_descriptor> = data
+            .iter()
+            .map(|s| {
+                assert!(
+                    s.len() <= generators.len(),
+                    "generators has a length smaller than the longest sequence in the input data"
+                );
+                s.into()
+            })
+            .collect();
 
-    let sxt_bn254_g1_generators = generators.as_ptr() as *const blitzar_sys::sxt_bn254_g1;
+        let sxt_grumpkin_generators = generators.as_ptr() as *const blitzar_sys::sxt_grumpkin;
 
-    let sxt_bn254_g1_uncompressed = commitments.as_mut_ptr() as *mut blitzar_sys::sxt_bn254_g1;
+        let sxt_grumpkin_uncompressed = commitments.as_mut_ptr() as *mut blitzar_sys::sxt_grumpkin;
 
-    unsafe {
-        blitzar_sys::sxt_bn254_g1_uncompressed_compute_pedersen_commitments_with_generators(
-            sxt_bn254_g1_uncompressed,
-            sxt_descriptors.len() as u32,
-            sxt_descriptors.as_ptr(),
-            sxt_bn254_g1_generators,
-        );
+        unsafe {
+            blitzar_sys::sxt_grumpkin_uncompressed_compute_pedersen_commitments_with_generators(
+                sxt_grumpkin_uncompressed,
+                sxt_descriptors.len() as u32,
+                sxt_descriptors.as_ptr(),
+                sxt_grumpkin_generators,
+            );
+        }
+        return;
     }
+
+    reference::compute_grumpkin_uncompressed_commitments_with_generators(
+        commitments,
+        data,
+        generators,
+    );
 }
 
 #[doc = include_str!("../../docs/commitments/update_curve25519_commitments.md")]
@@ -197,14 +267,64 @@ pub fn update_curve25519_commitments(
     compute_curve25519_commitments(&mut partial_commitments, data, offset_generators);
 
     commitments
-        .iter_mut()
-        .zip(partial_commitments)
+        .par_iter_mut()
+        .zip(partial_commitments.par_iter())
         .for_each(|(c_a, c_b)| {
-            *c_a = (c_a.decompress().unwrap_or_else(|| {
-                panic!("invalid ristretto point decompression on update_curve25519_commitments")
-            }) + c_b.decompress().unwrap_or_else(|| {
-                panic!("invalid ristretto point decompression on update_curve25519_commitments")
-            }))
-            .compress()
+            *c_a = (decompress_for_update(c_a) + decompress_for_update(c_b)).compress()
         });
 }
+
+/// Updates decompressed commitments using `curve25519` elements.
+///
+/// This is the same as [update_curve25519_commitments], except that the commitments
+/// are kept as [RistrettoPoint]s, so repeated updates of the same commitments
+/// do not decompress and recompress them every time. Start from
+/// [RistrettoPoint::default] (the identity) to compute new commitments, and
+/// compress the points once all updates are applied.
+///
+/// # Asserts
+///
+/// If the `data.len()` value is different from the `commitments.len()` value.
+pub fn update_curve25519_decompressed_commitments(
+    commitments: &mut [RistrettoPoint],
+    data: &[Sequence],
+    offset_generators: u64,
+) {
+    assert_eq!(data.len(), commitments.len());
+    let num_columns: usize = commitments.len();
+
+    let mut partial_commitments = vec![CompressedRistretto::default(); num_columns];
+
+    compute_curve25519_commitments(&mut partial_commitments, data, offset_generators);
+
+    commitments
+        .par_iter_mut()
+        .zip(partial_commitments.par_iter())
+        .for_each(|(c_a, c_b)| *c_a += decompress_for_update(c_b));
+}
+
+#[doc = include_str!("../../docs/commitments/verify_commitments.md")]
+pub fn verify_commitments(
+    claimed_commitments: &[CompressedRistretto],
+    data: &[Sequence],
+    offset_generators: u64,
+) -> Vec<usize> {
+    assert_eq!(data.len(), claimed_commitments.len());
+
+    let mut commitments = vec![CompressedRistretto::default(); data.len()];
+    compute_curve25519_commitments(&mut commitments, data, offset_generators);
+
+    claimed_commitments
+        .par_iter()
+        .zip(&commitments)
+        .enumerate()
+        .filter(|(_, (claimed, commitment))| claimed != commitment)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn decompress_for_update(commitment: &CompressedRistretto) -> RistrettoPoint {
+    commitment.decompress().unwrap_or_else(|| {
+        panic!("invalid ristretto point decompression on update_curve25519_commitments")
+    })
+}
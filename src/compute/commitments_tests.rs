@@ -141,6 +141,58 @@ fn we_can_update_multiple_commitments() {
         .all(|&c| c != CompressedRistretto::default()));
 }
 
+#[test]
+fn we_can_update_decompressed_commitments() {
+    let dense_data: Vec<Vec<u32>> = vec![vec![1, 0, 2, 0, 3], vec![7, 7]];
+    let scalar_data: Vec<Vec<Scalar>> = vec![
+        vec![Scalar::from(5000_u32), Scalar::from(1500_u32)],
+        vec![Scalar::from(3000_u32)],
+    ];
+    let expected_data: Vec<Vec<u32>> = vec![vec![1, 0, 2, 5000, 1503], vec![7, 7, 0, 3000]];
+    let dense_data_as_sequences: Vec<_> = dense_data.iter().map(|v| v.into()).collect();
+    let sliced_scalar_data: Vec<_> = scalar_data.iter().map(|v| v.into()).collect();
+    let expected_data_as_sequences: Vec<_> = expected_data.iter().map(|v| v.into()).collect();
+
+    let mut commitments = vec![RistrettoPoint::default(); 2];
+    update_curve25519_decompressed_commitments(&mut commitments, &dense_data_as_sequences, 0);
+    update_curve25519_decompressed_commitments(&mut commitments, &sliced_scalar_data, 3);
+
+    let mut expected_commitments = vec![CompressedRistretto::default(); 2];
+    compute_curve25519_commitments(&mut expected_commitments, &expected_data_as_sequences, 0);
+
+    let commitments: Vec<_> = commitments.iter().map(|c| c.compress()).collect();
+    assert_eq!(commitments, expected_commitments);
+}
+
+#[test]
+#[should_panic(expected = "invalid ristretto point decompression")]
+fn updating_invalid_commitments_panics() {
+    let data: Vec<u32> = vec![1, 2, 3];
+    let mut commitments = vec![CompressedRistretto([0xff; 32]); 1];
+    update_curve25519_commitments(&mut commitments, &[(&data).into()], 0);
+}
+
+#[test]
+fn verify_commitments_reports_mismatched_columns() {
+    let data: Vec<Vec<i32>> = vec![vec![1, -2, 3], vec![4], vec![], vec![5, 6]];
+    let sequences: Vec<_> = data.iter().map(|v| v.into()).collect();
+    let mut commitments = vec![CompressedRistretto::default(); data.len()];
+    compute_curve25519_commitments(&mut commitments, &sequences, 3);
+
+    assert!(verify_commitments(&commitments, &sequences, 3).is_empty());
+
+    let mut claimed = commitments.clone();
+    claimed.swap(0, 1);
+    claimed[3] = CompressedRistretto([0xff; 32]);
+    assert_eq!(verify_commitments(&claimed, &sequences, 3), vec![0, 1, 3]);
+
+    // the commitments depend on the generator offset
+    assert_eq!(
+        verify_commitments(&commitments, &sequences, 4),
+        vec![0, 1, 3]
+    );
+}
+
 #[test]
 fn compute_commitments_with_scalars_works() {
     // generate input table
@@ -544,6 +596,38 @@ fn sending_generators_to_gpu_produces_correct_bn254_g1_commitment_results() {
     assert_ne!(bn254_g1_affine::default(), commitments[0]);
 }
 
+#[test]
+fn sending_generators_to_gpu_produces_correct_grumpkin_commitment_results() {
+    // generate input table
+    let data: Vec<u64> = vec![2, 3, 1, 5, 4, 7, 6, 8, 9, 10];
+
+    // randomly obtain the generator points
+    let mut rng = ark_std::test_rng();
+    let generator_points: Vec<GrumpkinAffine> = (0..data.len())
+        .map(|_| GrumpkinAffine::rand(&mut rng))
+        .collect();
+
+    // initialize commitments
+    let mut commitments = vec![GrumpkinAffine::default(); 1];
+
+    // compute commitment in Blitzar
+    compute_grumpkin_uncompressed_commitments_with_generators(
+        &mut commitments,
+        &[(&data).into()],
+        &generator_points,
+    );
+
+    // convert data to scalar
+    let scalar_data: Vec<ark_bn254::Fq> = data.iter().map(|d| ark_bn254::Fq::from(*d)).collect();
+
+    // compute msm in Arkworks
+    let ark_commitment = GrumpkinProjective::msm(&generator_points, &scalar_data).unwrap();
+
+    // verify results
+    assert_eq!(commitments[0], ark_commitment.into_affine());
+    assert_ne!(GrumpkinAffine::default(), commitments[0]);
+}
+
 #[test]
 fn sending_generators_and_scalars_to_gpu_produces_correct_commitment_results() {
     // generate input table
@@ -0,0 +1,115 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! impl_compressed_commitment {
+    ($name:ident, $affine:ty, $num_bytes:literal, $curve:literal) => {
+        #[doc = concat!("Compressed ", $curve, " commitment.")]
+        ///
+        /// The bytes use the arkworks compressed point encoding. Decoding checks that
+        /// they encode a point on the curve and in the prime order subgroup, so a value
+        /// of this type always decompresses.
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub struct $name([u8; $num_bytes]);
+
+        impl $name {
+            /// Number of bytes of the compressed encoding.
+            pub const NUM_BYTES: usize = $num_bytes;
+
+            /// Decodes a commitment, checking that the bytes encode a valid point.
+            pub fn from_bytes(bytes: [u8; $num_bytes]) -> Result<Self, SerializationError> {
+                <$affine>::deserialize_compressed(&bytes[..]).map(|_| Self(bytes))
+            }
+
+            /// Returns the compressed encoding.
+            pub fn as_bytes(&self) -> &[u8; $num_bytes] {
+                &self.0
+            }
+
+            /// Returns the commitment as an affine point.
+            pub fn decompress(&self) -> $affine {
+                <$affine>::deserialize_compressed_unchecked(&self.0[..])
+                    .expect("commitment bytes were validated on construction")
+            }
+        }
+
+        impl From<&$affine> for $name {
+            fn from(point: &$affine) -> Self {
+                let mut bytes = [0_u8; $num_bytes];
+                point
+                    .serialize_compressed(&mut bytes[..])
+                    .expect("buffer has the size of a compressed point");
+                Self(bytes)
+            }
+        }
+
+        impl From<$affine> for $name {
+            fn from(point: $affine) -> Self {
+                Self::from(&point)
+            }
+        }
+
+        impl From<$name> for $affine {
+            fn from(commitment: $name) -> Self {
+                commitment.decompress()
+            }
+        }
+
+        impl TryFrom<[u8; $num_bytes]> for $name {
+            type Error = SerializationError;
+
+            fn try_from(bytes: [u8; $num_bytes]) -> Result<Self, Self::Error> {
+                Self::from_bytes(bytes)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = SerializationError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                let bytes: [u8; $num_bytes] = bytes
+                    .try_into()
+                    .map_err(|_| SerializationError::InvalidData)?;
+                Self::from_bytes(bytes)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.0[..].serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bytes = Vec::<u8>::deserialize(deserializer)?;
+                Self::try_from(&bytes[..]).map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+impl_compressed_commitment!(
+    CompressedBls12381G1Commitment,
+    ark_bls12_381::G1Affine,
+    48,
+    "`bls12-381` `G1`"
+);
+impl_compressed_commitment!(
+    CompressedBn254G1Commitment,
+    ark_bn254::G1Affine,
+    32,
+    "`bn254` `G1`"
+);
@@ -0,0 +1,69 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use ark_std::UniformRand;
+
+#[test]
+fn we_can_convert_bls12_381_commitments_to_and_from_bytes() {
+    let mut rng = ark_std::test_rng();
+    let point = ark_bls12_381::G1Affine::rand(&mut rng);
+    let commitment = CompressedBls12381G1Commitment::from(&point);
+    assert_eq!(commitment.decompress(), point);
+
+    let decoded = CompressedBls12381G1Commitment::from_bytes(*commitment.as_bytes()).unwrap();
+    assert_eq!(decoded, commitment);
+
+    // we cannot decode bytes that are not a point: x = 1 is not on the curve
+    let mut bytes = [0_u8; 48];
+    bytes[0] = 0x80;
+    bytes[47] = 1;
+    assert!(CompressedBls12381G1Commitment::from_bytes(bytes).is_err());
+    assert!(CompressedBls12381G1Commitment::try_from(&commitment.as_bytes()[..47]).is_err());
+}
+
+#[test]
+fn we_can_convert_bn254_commitments_to_and_from_bytes() {
+    let mut rng = ark_std::test_rng();
+    let point = ark_bn254::G1Affine::rand(&mut rng);
+    let commitment = CompressedBn254G1Commitment::from(point);
+    assert_eq!(ark_bn254::G1Affine::from(commitment), point);
+
+    let decoded = CompressedBn254G1Commitment::try_from(&commitment.as_bytes()[..]).unwrap();
+    assert_eq!(decoded, commitment);
+
+    // x = 4 is not on the curve
+    let mut bytes = [0_u8; 32];
+    bytes[0] = 4;
+    assert!(CompressedBn254G1Commitment::from_bytes(bytes).is_err());
+}
+
+#[test]
+fn we_can_serialize_and_validate_commitments_with_serde() {
+    let mut rng = ark_std::test_rng();
+    let commitment = CompressedBls12381G1Commitment::from(ark_bls12_381::G1Affine::rand(&mut rng));
+    let json = serde_json::to_string(&commitment).unwrap();
+    let decoded: CompressedBls12381G1Commitment = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, commitment);
+
+    // invalid points are rejected
+    let mut bytes = vec![0_u8; 48];
+    bytes[0] = 0x80;
+    bytes[47] = 1;
+    let json = serde_json::to_string(&bytes).unwrap();
+    assert!(serde_json::from_str::<CompressedBls12381G1Commitment>(&json).is_err());
+
+    // wrong lengths are rejected
+    let json = serde_json::to_string(&vec![0_u8; 31]).unwrap();
+    assert!(serde_json::from_str::<CompressedBn254G1Commitment>(&json).is_err());
+}
@@ -1,16 +1,50 @@
-use crate::compute::ElementP2;
+use crate::compute::{ElementP2, GrumpkinConfig};
 use curve25519_dalek::ristretto::RistrettoPoint;
 
+// Curve ids of blitzar-sys, repeated here so that the reference backend can be
+// built without it.
+const SXT_CURVE_RISTRETTO255: u32 = 0;
+const SXT_CURVE_BLS_381: u32 = 1;
+const SXT_CURVE_BN_254: u32 = 2;
+const SXT_CURVE_GRUMPKIN: u32 = 3;
+
+// Ids of the G2 curves, which blitzar-sys has no MSM for. They are out of the
+// range of the blitzar-sys ids, and handles for them use the reference backend.
+const CURVE_BLS_381_G2: u32 = 0x101;
+const CURVE_BN_254_G2: u32 = 0x102;
+
+// Returns whether blitzar-sys computes MSMs for the curve.
+#[cfg(feature = "blitzar-sys")]
+pub(crate) fn has_native_msm(curve_id: u32) -> bool {
+    curve_id <= SXT_CURVE_GRUMPKIN
+}
+
+// Curves with a backend MSM implementation.
+//
+// G2 configs (with extension-field coordinates) have no native MSM, so their
+// handles always compute MSMs with the reference backend.
 pub trait SwCurveConfig: ark_ec::short_weierstrass::SWCurveConfig {
     const CURVE_ID: u32;
 }
 
 impl SwCurveConfig for ark_bls12_381::g1::Config {
-    const CURVE_ID: u32 = blitzar_sys::SXT_CURVE_BLS_381;
+    const CURVE_ID: u32 = SXT_CURVE_BLS_381;
 }
 
 impl SwCurveConfig for ark_bn254::g1::Config {
-    const CURVE_ID: u32 = blitzar_sys::SXT_CURVE_BN_254;
+    const CURVE_ID: u32 = SXT_CURVE_BN_254;
+}
+
+impl SwCurveConfig for GrumpkinConfig {
+    const CURVE_ID: u32 = SXT_CURVE_GRUMPKIN;
+}
+
+impl SwCurveConfig for ark_bls12_381::g2::Config {
+    const CURVE_ID: u32 = CURVE_BLS_381_G2;
+}
+
+impl SwCurveConfig for ark_bn254::g2::Config {
+    const CURVE_ID: u32 = CURVE_BN_254_G2;
 }
 
 pub trait CurveId {
@@ -18,9 +52,24 @@ pub trait CurveId {
 }
 
 impl CurveId for RistrettoPoint {
-    const CURVE_ID: u32 = blitzar_sys::SXT_CURVE_RISTRETTO255;
+    const CURVE_ID: u32 = SXT_CURVE_RISTRETTO255;
 }
 
 impl<C: SwCurveConfig> CurveId for ElementP2<C> {
     const CURVE_ID: u32 = C::CURVE_ID;
 }
+
+#[cfg(all(test, feature = "blitzar-sys"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curve_ids_match_blitzar_sys() {
+        assert_eq!(SXT_CURVE_RISTRETTO255, blitzar_sys::SXT_CURVE_RISTRETTO255);
+        assert_eq!(SXT_CURVE_BLS_381, blitzar_sys::SXT_CURVE_BLS_381);
+        assert_eq!(SXT_CURVE_BN_254, blitzar_sys::SXT_CURVE_BN_254);
+        assert_eq!(SXT_CURVE_GRUMPKIN, blitzar_sys::SXT_CURVE_GRUMPKIN);
+        assert!(!has_native_msm(CURVE_BLS_381_G2));
+        assert!(!has_native_msm(CURVE_BN_254_G2));
+    }
+}
@@ -1,13 +1,19 @@
-use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
-use ark_ff::fields::Field;
+use ark_ec::short_weierstrass::{Affine, Projective, SWCurveConfig};
+use ark_ff::{batch_inversion, fields::Field, BitIteratorBE, PrimeField};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use ark_std::{One, Zero};
+use rayon::prelude::*;
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use std::convert::From;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
 /// Projective form for a short Weierstrass curve element.
 ///
 /// A point (x, y, z) represents the affine point (x / z, y / z) or
 /// the identity if z == 0
-#[derive(Clone)]
 pub struct ElementP2<P: SWCurveConfig> {
     /// (x, z) maps to the affine point x / z
     pub x: P::BaseField,
@@ -21,6 +27,16 @@ pub struct ElementP2<P: SWCurveConfig> {
     pub z: P::BaseField,
 }
 
+impl<P: SWCurveConfig> Clone for ElementP2<P> {
+    fn clone(&self) -> Self {
+        Self {
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+}
+
 impl<P: SWCurveConfig> Default for ElementP2<P> {
     fn default() -> Self {
         Self {
@@ -31,6 +47,138 @@ impl<P: SWCurveConfig> Default for ElementP2<P> {
     }
 }
 
+impl<P: SWCurveConfig> std::fmt::Debug for ElementP2<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ElementP2")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("z", &self.z)
+            .finish()
+    }
+}
+
+impl<P: SWCurveConfig> PartialEq for ElementP2<P> {
+    /// Two points are equal if they represent the same affine point,
+    /// which is checked by cross-multiplying with the other point's z.
+    fn eq(&self, other: &Self) -> bool {
+        match (self.z.is_zero(), other.z.is_zero()) {
+            (true, true) => true,
+            (false, false) => {
+                self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<P: SWCurveConfig> Eq for ElementP2<P> {}
+
+impl<P: SWCurveConfig> ElementP2<P> {
+    /// Returns `true` if the point represents the identity element.
+    pub fn is_zero(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    /// Checks that the point satisfies the projective curve equation
+    /// y^2 z = x^3 + a x z^2 + b z^3.
+    ///
+    /// The identity element is always on the curve.
+    pub fn is_on_curve(&self) -> bool {
+        if self.z.is_zero() {
+            return true;
+        }
+        let z2 = self.z.square();
+        let lhs = self.y.square() * self.z;
+        let rhs = self.x.square() * self.x + P::mul_by_a(self.x * z2) + P::COEFF_B * z2 * self.z;
+        lhs == rhs
+    }
+
+    /// Returns `2 * self`.
+    pub fn double(&self) -> Self {
+        self.add_points(self)
+    }
+
+    // Complete addition in homogeneous projective coordinates, following
+    // Algorithm 1 of Renes, Costello and Batina, "Complete addition formulas
+    // for prime order elliptic curves" (2015). The formulas handle doubling and
+    // inverse points, so only the z == 0 encoding of the identity, whose x and y
+    // coordinates are arbitrary, needs special casing.
+    fn add_points(&self, other: &Self) -> Self {
+        if self.z.is_zero() {
+            return other.clone();
+        }
+        if other.z.is_zero() {
+            return self.clone();
+        }
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (other.x, other.y, other.z);
+        let b3 = P::COEFF_B.double() + P::COEFF_B;
+
+        let mut t0 = x1 * x2;
+        let mut t1 = y1 * y2;
+        let mut t2 = z1 * z2;
+        let mut t3 = (x1 + y1) * (x2 + y2);
+        let mut t4 = t0 + t1;
+        t3 -= t4;
+        t4 = (x1 + z1) * (x2 + z2);
+        let mut t5 = t0 + t2;
+        t4 -= t5;
+        t5 = (y1 + z1) * (y2 + z2);
+        let mut x3 = t1 + t2;
+        t5 -= x3;
+        let mut z3 = P::mul_by_a(t4);
+        x3 = b3 * t2;
+        z3 += x3;
+        x3 = t1 - z3;
+        z3 += t1;
+        let mut y3 = x3 * z3;
+        t1 = t0.double() + t0;
+        t2 = P::mul_by_a(t2);
+        t4 *= b3;
+        t1 += t2;
+        t2 = P::mul_by_a(t0 - t2);
+        t4 += t2;
+        t0 = t1 * t4;
+        y3 += t0;
+        t0 = t5 * t4;
+        x3 *= t3;
+        x3 -= t0;
+        t0 = t3 * t1;
+        z3 *= t5;
+        z3 += t0;
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    /// Converts a slice of points to affine form.
+    ///
+    /// Uses Montgomery's trick so that all points share a single field inversion,
+    /// instead of one inversion per point as with `Affine::from`.
+    pub fn batch_normalize(points: &[Self]) -> Vec<Affine<P>> {
+        let mut z_invs: Vec<P::BaseField> = points.par_iter().map(|pt| pt.z).collect();
+        batch_inversion(&mut z_invs);
+        points
+            .par_iter()
+            .zip(z_invs)
+            .map(|(pt, z_inv)| {
+                if pt.z.is_zero() {
+                    Affine::<P>::identity()
+                } else {
+                    Affine::<P> {
+                        x: pt.x * z_inv,
+                        y: pt.y * z_inv,
+                        infinity: false,
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
 impl<P: SWCurveConfig> From<Affine<P>> for ElementP2<P> {
     fn from(pt: Affine<P>) -> Self {
         ElementP2::<P>::from(&pt)
@@ -74,3 +222,173 @@ impl<P: SWCurveConfig> From<&ElementP2<P>> for Affine<P> {
         }
     }
 }
+
+impl<P: SWCurveConfig> From<ElementP2<P>> for Projective<P> {
+    fn from(pt: ElementP2<P>) -> Self {
+        Projective::<P>::from(&pt)
+    }
+}
+
+impl<P: SWCurveConfig> From<&ElementP2<P>> for Projective<P> {
+    /// Converts to Jacobian coordinates without a field inversion.
+    ///
+    /// (x, y, z) maps to (x * z, y * z^2, z), since Jacobian coordinates
+    /// represent the affine point (X / Z^2, Y / Z^3).
+    fn from(pt: &ElementP2<P>) -> Self {
+        if pt.z.is_zero() {
+            return Projective::<P>::zero();
+        }
+        Projective::<P>::new_unchecked(pt.x * pt.z, pt.y * pt.z.square(), pt.z)
+    }
+}
+
+impl<P: SWCurveConfig> From<Projective<P>> for ElementP2<P> {
+    fn from(pt: Projective<P>) -> Self {
+        ElementP2::<P>::from(&pt)
+    }
+}
+
+impl<P: SWCurveConfig> From<&Projective<P>> for ElementP2<P> {
+    /// Converts from Jacobian coordinates without a field inversion.
+    ///
+    /// (X, Y, Z) maps to (X * Z, Y, Z^3).
+    fn from(pt: &Projective<P>) -> Self {
+        if pt.z.is_zero() {
+            return ElementP2::<P>::default();
+        }
+        Self {
+            x: pt.x * pt.z,
+            y: pt.y,
+            z: pt.z.square() * pt.z,
+        }
+    }
+}
+
+impl<P: SWCurveConfig> Neg for ElementP2<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: self.x,
+            y: -self.y,
+            z: self.z,
+        }
+    }
+}
+
+impl<P: SWCurveConfig> Add<&ElementP2<P>> for &ElementP2<P> {
+    type Output = ElementP2<P>;
+
+    fn add(self, other: &ElementP2<P>) -> ElementP2<P> {
+        self.add_points(other)
+    }
+}
+
+impl<P: SWCurveConfig> Add for ElementP2<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.add_points(&other)
+    }
+}
+
+impl<P: SWCurveConfig> AddAssign<&ElementP2<P>> for ElementP2<P> {
+    fn add_assign(&mut self, other: &Self) {
+        *self = self.add_points(other);
+    }
+}
+
+impl<P: SWCurveConfig> Sub<&ElementP2<P>> for &ElementP2<P> {
+    type Output = ElementP2<P>;
+
+    fn sub(self, other: &ElementP2<P>) -> ElementP2<P> {
+        self.add_points(&-other.clone())
+    }
+}
+
+impl<P: SWCurveConfig> Sub for ElementP2<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.add_points(&-other)
+    }
+}
+
+impl<P: SWCurveConfig> SubAssign<&ElementP2<P>> for ElementP2<P> {
+    fn sub_assign(&mut self, other: &Self) {
+        *self = &*self - other;
+    }
+}
+
+impl<P: SWCurveConfig> Mul<&P::ScalarField> for &ElementP2<P> {
+    type Output = ElementP2<P>;
+
+    /// Double-and-add scalar multiplication. This is not constant time.
+    fn mul(self, scalar: &P::ScalarField) -> ElementP2<P> {
+        let mut res = ElementP2::<P>::default();
+        for bit in BitIteratorBE::without_leading_zeros(scalar.into_bigint()) {
+            res = res.double();
+            if bit {
+                res = res.add_points(self);
+            }
+        }
+        res
+    }
+}
+
+impl<P: SWCurveConfig> Mul<P::ScalarField> for ElementP2<P> {
+    type Output = Self;
+
+    fn mul(self, scalar: P::ScalarField) -> Self {
+        &self * &scalar
+    }
+}
+
+impl<P: SWCurveConfig> Valid for ElementP2<P> {
+    fn check(&self) -> Result<(), SerializationError> {
+        Affine::<P>::from(self).check()
+    }
+}
+
+/// Points are serialized in affine form, exactly like `Affine<P>`.
+impl<P: SWCurveConfig> CanonicalSerialize for ElementP2<P> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        Affine::<P>::from(self).serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        Affine::<P>::identity().serialized_size(compress)
+    }
+}
+
+impl<P: SWCurveConfig> CanonicalDeserialize for ElementP2<P> {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Affine::<P>::deserialize_with_mode(reader, compress, validate).map(Self::from)
+    }
+}
+
+/// Points are serialized as the bytes of their compressed affine form.
+impl<P: SWCurveConfig> Serialize for ElementP2<P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut bytes = Vec::new();
+        self.serialize_compressed(&mut bytes)
+            .map_err(S::Error::custom)?;
+        bytes.serialize(serializer)
+    }
+}
+
+/// Deserialized points are checked to be on the curve and in the prime order subgroup.
+impl<'de, P: SWCurveConfig> Deserialize<'de> for ElementP2<P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Self::deserialize_compressed(&bytes[..]).map_err(D::Error::custom)
+    }
+}
@@ -1,6 +1,7 @@
 use super::*;
-use ark_bls12_381::G1Affine;
-use ark_std::UniformRand;
+use ark_bls12_381::{G1Affine, G1Projective};
+use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_std::{One, UniformRand, Zero};
 
 #[test]
 fn we_can_convert_between_different_point_representations() {
@@ -17,3 +18,174 @@ fn we_can_convert_between_different_point_representations() {
     let e1p = G1Affine::from(e2);
     assert_eq!(e1, e1p);
 }
+
+#[test]
+fn we_can_convert_g2_points_with_extension_field_coordinates() {
+    let mut rng = ark_std::test_rng();
+
+    let e1 = ark_bls12_381::G2Affine::rand(&mut rng);
+    let e2 = ElementP2::from(e1);
+    assert_eq!(e1, ark_bls12_381::G2Affine::from(e2));
+
+    let e1 = ark_bn254::G2Affine::rand(&mut rng);
+    let e2 = ElementP2::from(e1);
+    assert_eq!(e1, ark_bn254::G2Affine::from(e2));
+
+    let e1 = ark_bn254::G2Affine::identity();
+    let e2 = ElementP2::from(e1);
+    assert_eq!(e1, ark_bn254::G2Affine::from(e2));
+}
+
+#[test]
+fn we_can_batch_normalize_points() {
+    let mut rng = ark_std::test_rng();
+    let mut points: Vec<ElementP2<ark_bls12_381::g1::Config>> = (0..5)
+        .map(|_| {
+            let mut pt = ElementP2::from(G1Affine::rand(&mut rng));
+            // scale the coordinates so that z != 1
+            let s = ark_bls12_381::Fq::rand(&mut rng);
+            pt.x *= s;
+            pt.y *= s;
+            pt.z *= s;
+            pt
+        })
+        .collect();
+    points.insert(2, ElementP2::default());
+
+    let expected: Vec<G1Affine> = points.iter().map(G1Affine::from).collect();
+    assert_eq!(ElementP2::batch_normalize(&points), expected);
+    assert!(expected[2].is_zero());
+
+    assert!(ElementP2::<ark_bls12_381::g1::Config>::batch_normalize(&[]).is_empty());
+}
+
+#[test]
+fn we_can_convert_between_element_p2_and_projective_points() {
+    let mut rng = ark_std::test_rng();
+
+    let p = G1Projective::rand(&mut rng);
+    let e = ElementP2::from(p);
+    assert_eq!(G1Affine::from(&e), p.into_affine());
+    assert_eq!(G1Projective::from(&e), p);
+
+    let mut e = ElementP2::from(G1Affine::rand(&mut rng));
+    let s = ark_bls12_381::Fq::rand(&mut rng);
+    e.x *= s;
+    e.y *= s;
+    e.z *= s;
+    assert_eq!(G1Projective::from(&e).into_affine(), G1Affine::from(&e));
+
+    // we handle zero
+    let e = ElementP2::<ark_bls12_381::g1::Config>::default();
+    assert!(G1Projective::from(e).is_zero());
+    assert!(ElementP2::from(G1Projective::zero()).z.is_zero());
+}
+
+fn we_can_do_group_arithmetic<P: ark_ec::short_weierstrass::SWCurveConfig>() {
+    let mut rng = ark_std::test_rng();
+    let a = ark_ec::short_weierstrass::Projective::<P>::rand(&mut rng);
+    let b = ark_ec::short_weierstrass::Projective::<P>::rand(&mut rng);
+    let s = P::ScalarField::rand(&mut rng);
+    let ea = ElementP2::<P>::from(a);
+    let eb = ElementP2::<P>::from(b);
+    let zero = ElementP2::<P>::default();
+
+    assert!(ea.is_on_curve());
+    assert!(zero.is_on_curve());
+    assert_eq!(ElementP2::from(a + b), &ea + &eb);
+    assert_eq!(ElementP2::from(a - b), &ea - &eb);
+    assert_eq!(ElementP2::from(-a), -ea.clone());
+    assert_eq!(ElementP2::from(a.double()), ea.double());
+    assert_eq!(ElementP2::from(a.double()), &ea + &ea);
+    assert_eq!(ElementP2::from(a * s), &ea * &s);
+    let difference = &ea - &ElementP2::from(a);
+    assert!(difference.is_zero());
+    assert!(difference.is_on_curve());
+
+    // the identity is neutral
+    assert_eq!(&ea + &zero, ea);
+    assert_eq!(&zero + &ea, ea);
+    assert_eq!(zero.clone() + zero.clone(), zero);
+    assert_eq!(&zero * &s, zero);
+
+    // equality does not depend on the representation
+    let mut scaled = ea.clone();
+    scaled.x *= P::BaseField::from(3_u64);
+    scaled.y *= P::BaseField::from(3_u64);
+    scaled.z *= P::BaseField::from(3_u64);
+    assert_eq!(scaled, ea);
+    assert_ne!(ea, eb);
+    assert_ne!(ea, zero);
+
+    // a point off the curve is detected
+    let mut off_curve = ea.clone();
+    off_curve.y += P::BaseField::one();
+    assert!(!off_curve.is_on_curve());
+}
+
+#[test]
+fn we_can_do_group_arithmetic_on_bls12_381_points() {
+    we_can_do_group_arithmetic::<ark_bls12_381::g1::Config>();
+    we_can_do_group_arithmetic::<ark_bls12_381::g2::Config>();
+}
+
+#[test]
+fn we_can_do_group_arithmetic_on_bn254_points() {
+    we_can_do_group_arithmetic::<ark_bn254::g1::Config>();
+    we_can_do_group_arithmetic::<ark_bn254::g2::Config>();
+}
+
+#[test]
+fn we_can_do_group_arithmetic_on_grumpkin_points() {
+    we_can_do_group_arithmetic::<GrumpkinConfig>();
+}
+
+#[test]
+fn we_can_serialize_points_in_affine_form() {
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+    let mut rng = ark_std::test_rng();
+    let mut e = ElementP2::from(G1Affine::rand(&mut rng));
+    let s = ark_bls12_381::Fq::rand(&mut rng);
+    e.x *= s;
+    e.y *= s;
+    e.z *= s;
+
+    // the encoding is the one of the affine point
+    let mut bytes = Vec::new();
+    e.serialize_compressed(&mut bytes).unwrap();
+    let mut affine_bytes = Vec::new();
+    G1Affine::from(&e)
+        .serialize_compressed(&mut affine_bytes)
+        .unwrap();
+    assert_eq!(bytes, affine_bytes);
+    assert_eq!(e.compressed_size(), bytes.len());
+
+    let decoded =
+        ElementP2::<ark_bls12_381::g1::Config>::deserialize_compressed(&bytes[..]).unwrap();
+    assert_eq!(decoded, e);
+
+    let mut bytes = Vec::new();
+    e.serialize_uncompressed(&mut bytes).unwrap();
+    let decoded =
+        ElementP2::<ark_bls12_381::g1::Config>::deserialize_uncompressed(&bytes[..]).unwrap();
+    assert_eq!(decoded, e);
+
+    // serde uses the compressed encoding
+    let json = serde_json::to_string(&e).unwrap();
+    let decoded: ElementP2<ark_bls12_381::g1::Config> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, e);
+
+    // the identity round trips
+    let zero = ElementP2::<ark_bn254::g1::Config>::default();
+    let json = serde_json::to_string(&zero).unwrap();
+    let decoded: ElementP2<ark_bn254::g1::Config> = serde_json::from_str(&json).unwrap();
+    assert!(decoded.is_zero());
+
+    // invalid points are rejected: x = 1 is not on the curve
+    let mut bytes = vec![0_u8; 48];
+    bytes[0] = 0x80;
+    bytes[47] = 1;
+    let json = serde_json::to_string(&bytes).unwrap();
+    assert!(serde_json::from_str::<ElementP2<ark_bls12_381::g1::Config>>(&json).is_err());
+}
@@ -0,0 +1,135 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use thiserror::Error;
+
+/// MsmError related to multi-scalar multiplication handles
+#[derive(Error, Debug)]
+pub enum MsmError {
+    /// This error occurs when reading or writing a handle file fails.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// This error occurs when a handle file is not a serialized handle.
+    #[error("Invalid handle file")]
+    InvalidFile,
+
+    /// This error occurs when a handle file was written by an incompatible format version.
+    #[error("Unsupported handle file version {0}")]
+    UnsupportedVersion(u32),
+
+    /// This error occurs when a handle file was written for a different curve.
+    #[error("Handle file curve mismatch: expected curve {expected}, found curve {found}")]
+    CurveMismatch {
+        /// The curve of the requested handle
+        expected: u32,
+        /// The curve stored in the file
+        found: u32,
+    },
+
+    /// This error occurs when a handle file was written by a different backend than
+    /// the one that loads it.
+    #[error("Handle file backend mismatch: expected backend {expected}, found backend {found}")]
+    BackendMismatch {
+        /// The backend that loads the handle: 0 for blitzar-sys, 1 for the reference backend
+        expected: u32,
+        /// The backend stored in the file
+        found: u32,
+    },
+
+    /// This error occurs when the contents of a handle file do not match its checksum.
+    #[error("Handle file checksum mismatch")]
+    ChecksumMismatch,
+
+    /// This error occurs when the backend fails to create a handle.
+    #[error("The backend failed to create the handle")]
+    HandleCreationFailed,
+
+    /// This error occurs when an MSM is longer than the number of generators of the handle.
+    #[error("MSM length {length} exceeds the {num_generators} generators of the handle")]
+    LengthExceedsGenerators {
+        /// The length of the requested MSM
+        length: usize,
+        /// The number of generators of the handle
+        num_generators: usize,
+    },
+
+    /// This error occurs when the scalar buffer does not match the requested layout.
+    #[error("The scalar buffer length does not match the requested layout")]
+    ScalarsLengthMismatch,
+
+    /// This error occurs when a scalar size is zero or larger than 256 bits.
+    #[error("Invalid scalar size")]
+    InvalidScalarSize,
+
+    /// This error occurs when a per-output table does not have one entry per output.
+    #[error("Expected {expected} per-output entries, found {found}")]
+    OutputTableLengthMismatch {
+        /// The number of outputs
+        expected: usize,
+        /// The number of entries in the table
+        found: usize,
+    },
+
+    /// This error occurs when variable length MSM lengths are not sorted in ascending order.
+    #[error("Output lengths must be sorted in ascending order")]
+    UnsortedOutputLengths,
+}
+
+/// CommitmentError related to arithmetic on commitments
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CommitmentError {
+    /// This error occurs when a commitment does not decompress to a valid point.
+    #[error("Commitment {index} of operand {operand} is not a valid point")]
+    InvalidCommitment {
+        /// The input batch holding the commitment: 0 for `lhs` or the only batch of
+        /// commitments, 1 for `rhs`
+        operand: usize,
+        /// The index of the commitment in its batch
+        index: usize,
+    },
+
+    /// This error occurs when the inputs of a batch operation have different lengths.
+    #[error("Expected {expected} entries, found {found}")]
+    LengthMismatch {
+        /// The number of commitments
+        expected: usize,
+        /// The number of entries of the other input
+        found: usize,
+    },
+
+    /// This error occurs when a row index has no generator.
+    #[error("Row {row} is out of the range of the {num_generators} generators")]
+    RowOutOfRange {
+        /// The row index
+        row: u64,
+        /// The number of generators
+        num_generators: u64,
+    },
+
+    /// This error occurs when a row is given more than once.
+    #[error("Row {row} is given more than once")]
+    DuplicateRow {
+        /// The row index
+        row: u64,
+    },
+
+    /// This error occurs when the generator index of a row does not fit in a `u64`.
+    #[error("Row {row} at generator offset {offset_generators} overflows the generator index")]
+    GeneratorIndexOverflow {
+        /// The row index
+        row: u64,
+        /// The generator offset of the rows
+        offset_generators: u64,
+    },
+}
@@ -0,0 +1,192 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::fixed_msm::{count_msm_rows, count_packed_rows, count_row_bytes, count_vlen_rows};
+use crate::compute::{curve::SwCurveConfig, CurveId, ElementP2, MsmError, MsmHandle, SwMsmHandle};
+use ark_ec::short_weierstrass::Affine;
+use std::ops::{AddAssign, Range};
+
+/// Handle to compute MSMs over a generator set that can grow.
+///
+/// The generators are stored as a chain of [MsmHandle] segments. [ExtendableMsmHandle::extend]
+/// appends a new segment, so only the new generators pay for precomputation. MSMs
+/// split the scalars by segment, compute one MSM per segment, and add the results.
+///
+/// The scalar layouts are the same as for [MsmHandle::msm], [MsmHandle::packed_msm],
+/// and [MsmHandle::vlen_msm]. Each MSM costs one backend call per segment it spans,
+/// so a handle that was extended many times can be rebuilt with
+/// [ExtendableMsmHandle::new] to merge its segments.
+pub struct ExtendableMsmHandle<T: CurveId> {
+    segments: Vec<MsmHandle<T>>,
+}
+
+impl<T> ExtendableMsmHandle<T>
+where
+    T: CurveId + Clone + Default + for<'a> AddAssign<&'a T>,
+{
+    /// New handle from the specified generators.
+    pub fn new(generators: &[T]) -> Self {
+        let mut handle = Self {
+            segments: Vec::new(),
+        };
+        handle.extend(generators);
+        handle
+    }
+
+    /// Appends generators to the handle.
+    ///
+    /// Only the appended generators are precomputed.
+    pub fn extend(&mut self, generators: &[T]) {
+        if !generators.is_empty() {
+            self.segments.push(MsmHandle::new(generators));
+        }
+    }
+
+    /// Number of generators of the handle, over all segments.
+    pub fn num_generators(&self) -> usize {
+        self.segments.iter().map(MsmHandle::num_generators).sum()
+    }
+
+    /// Number of underlying [MsmHandle] segments.
+    pub fn num_segments(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Compute an MSM, see [MsmHandle::msm].
+    pub fn msm(
+        &self,
+        res: &mut [T],
+        element_num_bytes: u32,
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let n = count_msm_rows(res.len(), element_num_bytes, scalars.len())?;
+        let row_num_bytes = res.len() * element_num_bytes as usize;
+        self.sum_segments(res, n, |segment, rows, partial| {
+            let scalars = &scalars[rows.start * row_num_bytes..rows.end * row_num_bytes];
+            segment.msm(partial, element_num_bytes, scalars)
+        })
+    }
+
+    /// Compute a packed MSM, see [MsmHandle::packed_msm].
+    pub fn packed_msm(
+        &self,
+        res: &mut [T],
+        output_bit_table: &[u32],
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let n = count_packed_rows(res.len(), output_bit_table, scalars.len())?;
+        let row_num_bytes = count_row_bytes(output_bit_table);
+        self.sum_segments(res, n, |segment, rows, partial| {
+            let scalars = &scalars[rows.start * row_num_bytes..rows.end * row_num_bytes];
+            segment.packed_msm(partial, output_bit_table, scalars)
+        })
+    }
+
+    /// Compute a variable length MSM, see [MsmHandle::vlen_msm].
+    pub fn vlen_msm(
+        &self,
+        res: &mut [T],
+        output_bit_table: &[u32],
+        output_lengths: &[u32],
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let n = count_vlen_rows(res.len(), output_bit_table, output_lengths, scalars.len())?;
+        let row_num_bytes = count_row_bytes(output_bit_table);
+        self.sum_segments(res, n, |segment, rows, partial| {
+            let scalars = &scalars[rows.start * row_num_bytes..rows.end * row_num_bytes];
+            let output_lengths: Vec<u32> = output_lengths
+                .iter()
+                .map(|length| (*length as usize).clamp(rows.start, rows.end) - rows.start)
+                .map(|length| length as u32)
+                .collect();
+            segment.vlen_msm(partial, output_bit_table, &output_lengths, scalars)
+        })
+    }
+
+    // Runs `f` on every segment that overlaps the first `n` rows, with the range of
+    // rows covered by the segment, and adds up the results.
+    fn sum_segments(
+        &self,
+        res: &mut [T],
+        n: usize,
+        f: impl Fn(&MsmHandle<T>, Range<usize>, &mut [T]) -> Result<(), MsmError>,
+    ) -> Result<(), MsmError> {
+        if n > self.num_generators() {
+            return Err(MsmError::LengthExceedsGenerators {
+                length: n,
+                num_generators: self.num_generators(),
+            });
+        }
+        let mut partial = vec![T::default(); res.len()];
+        res.fill(T::default());
+        let mut offset = 0;
+        for segment in &self.segments {
+            if offset >= n {
+                break;
+            }
+            let end = n.min(offset + segment.num_generators());
+            f(segment, offset..end, &mut partial)?;
+            for (res_i, partial_i) in res.iter_mut().zip(&partial) {
+                *res_i += partial_i;
+            }
+            offset = end;
+        }
+        Ok(())
+    }
+}
+
+impl<C: SwCurveConfig + Clone> SwMsmHandle for ExtendableMsmHandle<ElementP2<C>> {
+    type AffineElement = Affine<C>;
+
+    fn new_with_affine(generators: &[Self::AffineElement]) -> Self {
+        let generators: Vec<ElementP2<C>> = generators.iter().map(|e| e.into()).collect();
+        ExtendableMsmHandle::new(&generators)
+    }
+
+    fn affine_msm(
+        &self,
+        res: &mut [Self::AffineElement],
+        element_num_bytes: u32,
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let mut res_p: Vec<ElementP2<C>> = vec![ElementP2::<C>::default(); res.len()];
+        self.msm(&mut res_p, element_num_bytes, scalars)?;
+        res.copy_from_slice(&ElementP2::batch_normalize(&res_p));
+        Ok(())
+    }
+
+    fn affine_packed_msm(
+        &self,
+        res: &mut [Self::AffineElement],
+        output_bit_table: &[u32],
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let mut res_p: Vec<ElementP2<C>> = vec![ElementP2::<C>::default(); res.len()];
+        self.packed_msm(&mut res_p, output_bit_table, scalars)?;
+        res.copy_from_slice(&ElementP2::batch_normalize(&res_p));
+        Ok(())
+    }
+
+    fn affine_vlen_msm(
+        &self,
+        res: &mut [Self::AffineElement],
+        output_bit_table: &[u32],
+        output_lengths: &[u32],
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let mut res_p: Vec<ElementP2<C>> = vec![ElementP2::<C>::default(); res.len()];
+        self.vlen_msm(&mut res_p, output_bit_table, output_lengths, scalars)?;
+        res.copy_from_slice(&ElementP2::batch_normalize(&res_p));
+        Ok(())
+    }
+}
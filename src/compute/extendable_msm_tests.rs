@@ -0,0 +1,119 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use ark_bls12_381::G1Affine;
+use ark_std::UniformRand;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use rand_core::OsRng;
+
+fn random_generators(n: usize) -> Vec<RistrettoPoint> {
+    let mut rng = OsRng;
+    (0..n).map(|_| RistrettoPoint::random(&mut rng)).collect()
+}
+
+#[test]
+fn an_extended_handle_computes_the_same_msms_as_a_single_handle() {
+    let generators = random_generators(6);
+    let handle = MsmHandle::new(&generators);
+    let mut extendable = ExtendableMsmHandle::new(&generators[..2]);
+    extendable.extend(&generators[2..3]);
+    extendable.extend(&[]);
+    extendable.extend(&generators[3..]);
+    assert_eq!(extendable.num_generators(), 6);
+    assert_eq!(extendable.num_segments(), 3);
+
+    // 2 outputs of 1 byte each, over 5 generators spanning all segments
+    let scalars: Vec<u8> = (1..=10).collect();
+    let mut expected = vec![RistrettoPoint::default(); 2];
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle.msm(&mut expected, 1, &scalars).unwrap();
+    extendable.msm(&mut res, 1, &scalars).unwrap();
+    assert_eq!(res, expected);
+
+    // a prefix that only uses the first segment
+    handle.msm(&mut expected, 1, &scalars[..2]).unwrap();
+    extendable.msm(&mut res, 1, &scalars[..2]).unwrap();
+    assert_eq!(res, expected);
+
+    // packed scalars
+    let output_bit_table = [11, 5];
+    handle
+        .packed_msm(&mut expected, &output_bit_table, &scalars)
+        .unwrap();
+    extendable
+        .packed_msm(&mut res, &output_bit_table, &scalars)
+        .unwrap();
+    assert_eq!(res, expected);
+
+    // variable lengths, including one that ends inside a segment
+    let output_lengths = [1, 4];
+    handle
+        .vlen_msm(
+            &mut expected,
+            &output_bit_table,
+            &output_lengths,
+            &scalars[..8],
+        )
+        .unwrap();
+    extendable
+        .vlen_msm(&mut res, &output_bit_table, &output_lengths, &scalars[..8])
+        .unwrap();
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_compute_msms_longer_than_an_extendable_handle() {
+    let generators = random_generators(3);
+    let mut extendable = ExtendableMsmHandle::new(&[]);
+    let mut res = vec![RistrettoPoint::default(); 1];
+    assert!(matches!(
+        extendable.msm(&mut res, 1, &[1]),
+        Err(MsmError::LengthExceedsGenerators {
+            length: 1,
+            num_generators: 0
+        })
+    ));
+
+    extendable.extend(&generators);
+    extendable.msm(&mut res, 1, &[1]).unwrap();
+    assert_eq!(res[0], generators[0]);
+    assert!(matches!(
+        extendable.msm(&mut res, 1, &[1, 2, 3, 4]),
+        Err(MsmError::LengthExceedsGenerators { .. })
+    ));
+}
+
+#[test]
+fn we_can_compute_affine_msms_with_an_extendable_handle() {
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<G1Affine> = (0..4).map(|_| G1Affine::rand(&mut rng)).collect();
+    let handle = MsmHandle::<ElementP2<ark_bls12_381::g1::Config>>::new_with_affine(&generators);
+    let mut extendable =
+        ExtendableMsmHandle::<ElementP2<ark_bls12_381::g1::Config>>::new_with_affine(
+            &generators[..1],
+        );
+    extendable.extend(
+        &generators[1..]
+            .iter()
+            .map(ElementP2::from)
+            .collect::<Vec<_>>(),
+    );
+
+    let scalars: Vec<u8> = (1..=8).collect();
+    let mut expected = vec![G1Affine::default(); 2];
+    let mut res = vec![G1Affine::default(); 2];
+    handle.affine_msm(&mut expected, 1, &scalars).unwrap();
+    extendable.affine_msm(&mut res, 1, &scalars).unwrap();
+    assert_eq!(res, expected);
+}
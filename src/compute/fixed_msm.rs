@@ -1,14 +1,367 @@
-use super::backend::init_backend;
-use crate::compute::{curve::SwCurveConfig, CurveId, ElementP2};
+use super::reference::MsmGenerators;
+#[cfg(feature = "blitzar-sys")]
+use super::{backend::use_reference, curve::has_native_msm};
+use crate::{
+    compute::{curve::SwCurveConfig, CurveId, ElementP2, MsmError},
+    sequence::Sequence,
+};
 use ark_ec::short_weierstrass::Affine;
+use ark_ff::{BigInteger, PrimeField};
+use curve25519_dalek::scalar::Scalar;
 use rayon::prelude::*;
-use std::marker::PhantomData;
+use sha3::{Digest, Keccak256};
+#[cfg(feature = "blitzar-sys")]
+use std::{
+    collections::hash_map::RandomState,
+    ffi::CString,
+    fs::OpenOptions,
+    hash::{BuildHasher, Hasher},
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    marker::PhantomData,
+    ops::{Add, SubAssign},
+    path::Path,
+};
 
-fn count_scalars_per_output(scalars_len: usize, output_bit_table: &[u32]) -> u32 {
+// identifies files written by MsmHandle::write_to_file
+const HANDLE_FILE_MAGIC: [u8; 8] = *b"BLTZMSMH";
+
+// bumped whenever the layout of handle files changes
+const HANDLE_FILE_VERSION: u32 = 2;
+
+// magic, version, curve id, element size, number of generators, backend, and checksum
+const HANDLE_FILE_HEADER_SIZE: usize = 8 + 4 + 4 + 4 + 4 + 4 + 32;
+
+// the backends whose data a handle file can hold
+#[cfg(feature = "blitzar-sys")]
+const NATIVE_BACKEND: u32 = 0;
+const REFERENCE_BACKEND: u32 = 1;
+
+/// Temporary file used to exchange handle data with the backend, removed on drop.
+///
+/// The file is created exclusively under a random name, readable only by its owner,
+/// so another user cannot swap it out between the checksum and the backend reading it.
+#[cfg(feature = "blitzar-sys")]
+struct TempFile(PathBuf);
+
+#[cfg(feature = "blitzar-sys")]
+impl TempFile {
+    fn create() -> io::Result<(Self, File)> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        loop {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+            let name = format!(
+                "blitzar-msm-handle-{}-{:016x}",
+                std::process::id(),
+                hasher.finish()
+            );
+            let path = std::env::temp_dir().join(name);
+
+            let mut options = OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            match options.open(&path) {
+                Ok(file) => return Ok((Self(path), file)),
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    fn c_path(&self) -> io::Result<CString> {
+        self.0
+            .to_str()
+            .and_then(|path| CString::new(path).ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "the temporary directory is not a valid C string",
+                )
+            })
+    }
+}
+
+#[cfg(feature = "blitzar-sys")]
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Writer that computes the checksum of a handle file while passing the payload on
+/// to `inner`, so that the payload is never held in memory.
+struct ChecksumWriter<W> {
+    hasher: Keccak256,
+    inner: W,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    fn new(header: &[u8], inner: W) -> Self {
+        let mut hasher = Keccak256::new();
+        hasher.update(header);
+        Self { hasher, inner }
+    }
+
+    fn finalize(mut self) -> io::Result<[u8; 32]> {
+        self.inner.flush()?;
+        Ok(self.hasher.finalize().into())
+    }
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// Returns the checksum of a handle file with the given header and payload.
+fn handle_file_checksum(header: &[u8], mut payload: impl Read) -> io::Result<[u8; 32]> {
+    let mut writer = ChecksumWriter::new(header, io::sink());
+    io::copy(&mut payload, &mut writer)?;
+    writer.finalize()
+}
+
+// Writes a handle file, copying the payload after the header and its checksum.
+fn write_handle_file(
+    path: &Path,
+    header: &[u8],
+    checksum: &[u8; 32],
+    mut payload: impl Read,
+) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(header)?;
+    file.write_all(checksum)?;
+    io::copy(&mut payload, &mut file)?;
+    file.flush()
+}
+
+fn count_scalars_per_output(scalars_len: usize, output_bit_table: &[u32]) -> Result<u32, MsmError> {
+    let num_output_bytes = count_row_bytes(output_bit_table);
+    if num_output_bytes == 0 || scalars_len % num_output_bytes != 0 {
+        return Err(MsmError::ScalarsLengthMismatch);
+    }
+    (scalars_len / num_output_bytes)
+        .try_into()
+        .map_err(|_| MsmError::ScalarsLengthMismatch)
+}
+
+// Returns the number of rows of the scalars of an MSM, see `MsmHandle::msm`.
+pub(super) fn count_msm_rows(
+    num_outputs: usize,
+    element_num_bytes: u32,
+    scalars_len: usize,
+) -> Result<usize, MsmError> {
+    if element_num_bytes == 0 || element_num_bytes > 32 {
+        return Err(MsmError::InvalidScalarSize);
+    }
+    let row_num_bytes = num_outputs * element_num_bytes as usize;
+    if row_num_bytes == 0 {
+        return if scalars_len == 0 {
+            Ok(0)
+        } else {
+            Err(MsmError::ScalarsLengthMismatch)
+        };
+    }
+    if scalars_len % row_num_bytes != 0 {
+        return Err(MsmError::ScalarsLengthMismatch);
+    }
+    Ok(scalars_len / row_num_bytes)
+}
+
+// Returns the number of rows of the scalars of a packed MSM, see `MsmHandle::packed_msm`.
+pub(super) fn count_packed_rows(
+    num_outputs: usize,
+    output_bit_table: &[u32],
+    scalars_len: usize,
+) -> Result<usize, MsmError> {
+    check_output_bit_table(output_bit_table, num_outputs)?;
+    if num_outputs == 0 && scalars_len == 0 {
+        return Ok(0);
+    }
+    count_scalars_per_output(scalars_len, output_bit_table).map(|n| n as usize)
+}
+
+// Returns the number of rows of the scalars of a variable length MSM,
+// see `MsmHandle::vlen_msm`.
+pub(super) fn count_vlen_rows(
+    num_outputs: usize,
+    output_bit_table: &[u32],
+    output_lengths: &[u32],
+    scalars_len: usize,
+) -> Result<usize, MsmError> {
+    check_output_bit_table(output_bit_table, num_outputs)?;
+    check_output_table(output_lengths, num_outputs)?;
+    if output_lengths.windows(2).any(|w| w[0] > w[1]) {
+        return Err(MsmError::UnsortedOutputLengths);
+    }
+    let n = output_lengths.last().copied().unwrap_or(0) as usize;
+    if scalars_len != count_row_bytes(output_bit_table) * n {
+        return Err(MsmError::ScalarsLengthMismatch);
+    }
+    Ok(n)
+}
+
+pub(super) fn count_row_bytes(output_bit_table: &[u32]) -> usize {
     let bit_sum: usize = output_bit_table.iter().map(|s| *s as usize).sum();
-    let num_output_bytes = (bit_sum + 7) / 8;
-    assert!(scalars_len % num_output_bytes == 0);
-    (scalars_len / num_output_bytes).try_into().unwrap()
+    bit_sum.div_ceil(8)
+}
+
+// Lays out rows of little-endian scalars column-major, as expected by `MsmHandle::msm`.
+//
+// Each row is a `(bytes, element_size)` pair. Scalars are zero-extended to
+// `element_num_bytes` and rows are zero-padded to the length of the longest row.
+fn transpose_rows(rows: &[(&[u8], usize)], element_num_bytes: usize) -> Vec<u8> {
+    let num_outputs = rows.len();
+    let n = rows
+        .iter()
+        .map(|(bytes, element_size)| bytes.len() / element_size)
+        .max()
+        .unwrap_or(0);
+    let mut scalars = vec![0_u8; n * num_outputs * element_num_bytes];
+    scalars
+        .par_chunks_mut(num_outputs * element_num_bytes)
+        .enumerate()
+        .for_each(|(generator_index, chunk)| {
+            for (output_index, (bytes, element_size)) in rows.iter().enumerate() {
+                let src = generator_index * element_size;
+                if src < bytes.len() {
+                    let dst = output_index * element_num_bytes;
+                    chunk[dst..dst + element_size].copy_from_slice(&bytes[src..src + element_size]);
+                }
+            }
+        });
+    scalars
+}
+
+// Splits a row-major matrix into `num_outputs` rows of equal length.
+fn split_rows<S>(scalars: &[S], num_outputs: usize) -> Result<Vec<&[S]>, MsmError> {
+    if num_outputs == 0 {
+        return if scalars.is_empty() {
+            Ok(Vec::new())
+        } else {
+            Err(MsmError::ScalarsLengthMismatch)
+        };
+    }
+    if scalars.len() % num_outputs != 0 {
+        return Err(MsmError::ScalarsLengthMismatch);
+    }
+    let n = scalars.len() / num_outputs;
+    if n == 0 {
+        return Ok(vec![scalars; num_outputs]);
+    }
+    Ok(scalars.chunks(n).collect())
+}
+
+// Reorders the packed columns of `scalars` so that output j of the result is
+// output `order[j]` of `scalars`, see `MsmHandle::packed_msm` for the layout.
+fn permute_packed_columns(scalars: &[u8], output_bit_table: &[u32], order: &[usize]) -> Vec<u8> {
+    let row_num_bytes = count_row_bytes(output_bit_table);
+    let mut offsets = Vec::with_capacity(output_bit_table.len());
+    let mut offset = 0;
+    for bits in output_bit_table {
+        offsets.push(offset);
+        offset += *bits as usize;
+    }
+    let mut permuted = vec![0_u8; scalars.len()];
+    if row_num_bytes == 0 {
+        return permuted;
+    }
+    permuted
+        .par_chunks_mut(row_num_bytes)
+        .zip(scalars.par_chunks(row_num_bytes))
+        .for_each(|(dst_row, src_row)| {
+            let mut dst = 0;
+            for &output_index in order {
+                let src = offsets[output_index];
+                let bits = output_bit_table[output_index] as usize;
+                if src % 8 == 0 && dst % 8 == 0 && bits % 8 == 0 {
+                    dst_row[dst / 8..(dst + bits) / 8]
+                        .copy_from_slice(&src_row[src / 8..(src + bits) / 8]);
+                } else {
+                    for bit in 0..bits {
+                        let value = (src_row[(src + bit) / 8] >> ((src + bit) % 8)) & 1;
+                        dst_row[(dst + bit) / 8] |= value << ((dst + bit) % 8);
+                    }
+                }
+                dst += bits;
+            }
+        });
+    permuted
+}
+
+fn check_output_table<S>(table: &[S], num_outputs: usize) -> Result<(), MsmError> {
+    if table.len() != num_outputs {
+        return Err(MsmError::OutputTableLengthMismatch {
+            expected: num_outputs,
+            found: table.len(),
+        });
+    }
+    Ok(())
+}
+
+fn check_output_bit_table(output_bit_table: &[u32], num_outputs: usize) -> Result<(), MsmError> {
+    check_output_table(output_bit_table, num_outputs)?;
+    if output_bit_table.iter().any(|bits| *bits > 256) {
+        return Err(MsmError::InvalidScalarSize);
+    }
+    Ok(())
+}
+
+// Flips the bits at `sign_bits` in every row of `row_num_bytes` bytes. For a w-bit
+// two's complement scalar v, flipping bit w - 1 gives the unsigned value v + 2^(w-1).
+fn flip_sign_bits(scalars: &mut [u8], row_num_bytes: usize, sign_bits: &[usize]) {
+    if row_num_bytes == 0 || sign_bits.is_empty() {
+        return;
+    }
+    scalars.par_chunks_mut(row_num_bytes).for_each(|row| {
+        for bit in sign_bits {
+            row[bit / 8] ^= 1 << (bit % 8);
+        }
+    });
+}
+
+// Subtracts 2^(bit_width-1) times `sum` from `res`, which removes the offset of a
+// signed output whose generators add up to `sum`.
+pub(super) fn remove_signed_offset<T>(res: &mut T, sum: &T, bit_width: u32)
+where
+    T: Clone + for<'a> SubAssign<&'a T>,
+    for<'a> &'a T: Add<&'a T, Output = T>,
+{
+    if bit_width == 0 {
+        return;
+    }
+    let mut offset = sum.clone();
+    for _ in 1..bit_width {
+        offset = &offset + &offset;
+    }
+    *res -= &offset;
+}
+
+// Prepends `start` rows of zero scalars, so that the first row of `scalars` is
+// multiplied by generator `start`.
+fn pad_scalars(start: usize, row_num_bytes: usize, scalars: &[u8]) -> Vec<u8> {
+    let mut padded = vec![0_u8; start * row_num_bytes + scalars.len()];
+    padded[start * row_num_bytes..].copy_from_slice(scalars);
+    padded
+}
+
+// Generators of a handle, as held by the backend that computes its MSMs.
+enum Handle {
+    #[cfg(feature = "blitzar-sys")]
+    Native(*mut blitzar_sys::sxt_multiexp_handle),
+    Reference(MsmGenerators),
 }
 
 /// Handle to compute multi-scalar multiplications (MSMs) with pre-specified generators
@@ -18,7 +371,8 @@ fn count_scalars_per_output(scalars_len: usize, output_bit_table: &[u32]) -> u32
 #[doc = include_str!("../../examples/simple_fixed_msm.rs")]
 ///```
 pub struct MsmHandle<T: CurveId> {
-    handle: *mut blitzar_sys::sxt_multiexp_handle,
+    handle: Handle,
+    num_generators: u32,
     phantom: PhantomData<T>,
 }
 
@@ -30,20 +384,216 @@ impl<T: CurveId> MsmHandle<T> {
     ///
     /// Note: any MSMs computed with the handle must have length less than or equal
     /// to the number of generators used to create the handle.
+    ///
+    /// The backend has no MSM for the G2 groups of BLS12-381 and BN254, so their
+    /// handles always use the reference backend.
+    ///
+    /// # Panics
+    ///
+    /// If there are more than `u32::MAX` generators or the backend fails to create the handle.
     pub fn new(generators: &[T]) -> Self {
-        init_backend();
+        assert!(generators.len() <= u32::MAX as usize);
 
-        unsafe {
-            let handle = blitzar_sys::sxt_multiexp_handle_new(
-                T::CURVE_ID,
-                generators.as_ptr() as *const std::ffi::c_void,
-                generators.len() as u32,
-            );
-            Self {
-                handle,
-                phantom: PhantomData,
+        #[cfg(feature = "blitzar-sys")]
+        if !use_reference() && has_native_msm(T::CURVE_ID) {
+            unsafe {
+                let handle = blitzar_sys::sxt_multiexp_handle_new(
+                    T::CURVE_ID,
+                    generators.as_ptr() as *const std::ffi::c_void,
+                    generators.len() as u32,
+                );
+                if handle.is_null() {
+                    panic!("Error during MsmHandle creation");
+                }
+                return Self {
+                    handle: Handle::Native(handle),
+                    num_generators: generators.len() as u32,
+                    phantom: PhantomData,
+                };
+            }
+        }
+
+        Self {
+            handle: Handle::Reference(MsmGenerators::new(generators)),
+            num_generators: generators.len() as u32,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Number of generators the handle was created with.
+    ///
+    /// This is the maximum length of any MSM computed with the handle.
+    pub fn num_generators(&self) -> usize {
+        self.num_generators as usize
+    }
+
+    /// Identifier of the handle's curve in the backend.
+    pub fn curve_id(&self) -> u32 {
+        T::CURVE_ID
+    }
+
+    fn check_length(&self, length: usize) -> Result<(), MsmError> {
+        if length > self.num_generators() {
+            return Err(MsmError::LengthExceedsGenerators {
+                length,
+                num_generators: self.num_generators(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Write the handle, including its precomputed data, to a file.
+    ///
+    /// The file starts with a header holding the curve, the size of the generator
+    /// type, the number of generators, and a Keccak-256 checksum of the contents,
+    /// followed by the data produced by the backend.
+    ///
+    /// The data differs between the native and the reference backend, so the header
+    /// also records the backend, and a file can only be loaded with the backend that
+    /// wrote it.
+    ///
+    /// The data of a native handle is streamed from the file written by the backend,
+    /// so it is never held in memory. The backend writes it to a file in
+    /// [std::env::temp_dir] first, so saving needs free temporary space equal to the
+    /// size of the handle and doubles its disk I/O.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), MsmError> {
+        let mut header = Vec::with_capacity(HANDLE_FILE_HEADER_SIZE - 32);
+        header.extend_from_slice(&HANDLE_FILE_MAGIC);
+        header.extend_from_slice(&HANDLE_FILE_VERSION.to_le_bytes());
+        header.extend_from_slice(&T::CURVE_ID.to_le_bytes());
+        header.extend_from_slice(&(std::mem::size_of::<T>() as u32).to_le_bytes());
+        header.extend_from_slice(&self.num_generators.to_le_bytes());
+        let backend = match &self.handle {
+            #[cfg(feature = "blitzar-sys")]
+            Handle::Native(_) => NATIVE_BACKEND,
+            Handle::Reference(_) => REFERENCE_BACKEND,
+        };
+        header.extend_from_slice(&backend.to_le_bytes());
+
+        match &self.handle {
+            #[cfg(feature = "blitzar-sys")]
+            Handle::Native(handle) => {
+                // the backend can only write to a file, which is streamed twice: once
+                // for the checksum and once to copy it after the header
+                let (temp_file, _) = TempFile::create()?;
+                unsafe {
+                    blitzar_sys::sxt_multiexp_handle_write_to_file(
+                        *handle,
+                        temp_file.c_path()?.as_ptr(),
+                    );
+                }
+                let checksum = handle_file_checksum(&header, File::open(&temp_file.0)?)?;
+                write_handle_file(path.as_ref(), &header, &checksum, File::open(&temp_file.0)?)?;
+            }
+            Handle::Reference(generators) => {
+                let payload = generators.to_bytes();
+                let checksum = handle_file_checksum(&header, payload.as_slice())?;
+                write_handle_file(path.as_ref(), &header, &checksum, payload.as_slice())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Load a handle written by [MsmHandle::write_to_file].
+    ///
+    /// The precomputed data is reused, so this avoids the cost of [MsmHandle::new].
+    /// Loading fails if the file was written for a different curve, by another
+    /// backend than the one this handle would use, or by an incompatible version, or
+    /// if its contents do not match the stored checksum.
+    ///
+    /// The data of a native handle is copied to a file in [std::env::temp_dir] for the
+    /// backend to load from, so loading needs free temporary space equal to the size
+    /// of the handle and doubles its disk I/O.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, MsmError> {
+        let mut file = BufReader::new(File::open(path)?);
+        let mut file_header = [0_u8; HANDLE_FILE_HEADER_SIZE];
+        match file.read_exact(&mut file_header) {
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                return Err(MsmError::InvalidFile)
+            }
+            res => res?,
+        }
+        if file_header[..8] != HANDLE_FILE_MAGIC {
+            return Err(MsmError::InvalidFile);
+        }
+        let read_u32 =
+            |offset: usize| u32::from_le_bytes(file_header[offset..offset + 4].try_into().unwrap());
+
+        let version = read_u32(8);
+        if version != HANDLE_FILE_VERSION {
+            return Err(MsmError::UnsupportedVersion(version));
+        }
+        let curve_id = read_u32(12);
+        if curve_id != T::CURVE_ID {
+            return Err(MsmError::CurveMismatch {
+                expected: T::CURVE_ID,
+                found: curve_id,
+            });
+        }
+        if read_u32(16) as usize != std::mem::size_of::<T>() {
+            return Err(MsmError::InvalidFile);
+        }
+        let num_generators = read_u32(20);
+        let backend = Self::loading_backend();
+        if read_u32(24) != backend {
+            return Err(MsmError::BackendMismatch {
+                expected: backend,
+                found: read_u32(24),
+            });
+        }
+        let (header, checksum) = file_header.split_at(HANDLE_FILE_HEADER_SIZE - 32);
+
+        #[cfg(feature = "blitzar-sys")]
+        if backend == NATIVE_BACKEND {
+            // the payload is streamed to the file the backend loads from, and
+            // checked before the backend reads it
+            let (temp_file, temp) = TempFile::create()?;
+            let mut writer = ChecksumWriter::new(header, BufWriter::new(temp));
+            io::copy(&mut file, &mut writer)?;
+            if writer.finalize()? != checksum {
+                return Err(MsmError::ChecksumMismatch);
+            }
+            let handle = unsafe {
+                blitzar_sys::sxt_multiexp_handle_new_from_file(
+                    T::CURVE_ID,
+                    temp_file.c_path()?.as_ptr(),
+                )
+            };
+            if handle.is_null() {
+                return Err(MsmError::HandleCreationFailed);
             }
+            return Ok(Self {
+                handle: Handle::Native(handle),
+                num_generators,
+                phantom: PhantomData,
+            });
+        }
+
+        // the reference generators are held in memory anyway
+        let mut payload = Vec::new();
+        file.read_to_end(&mut payload)?;
+        if handle_file_checksum(header, payload.as_slice())? != checksum {
+            return Err(MsmError::ChecksumMismatch);
+        }
+        let generators =
+            MsmGenerators::from_bytes(curve_id, &payload).ok_or(MsmError::InvalidFile)?;
+        if generators.len() != num_generators as usize {
+            return Err(MsmError::InvalidFile);
         }
+        Ok(Self {
+            handle: Handle::Reference(generators),
+            num_generators,
+            phantom: PhantomData,
+        })
+    }
+
+    // Returns the backend of the handles created or loaded for the curve.
+    fn loading_backend() -> u32 {
+        #[cfg(feature = "blitzar-sys")]
+        if !use_reference() && has_native_msm(T::CURVE_ID) {
+            return NATIVE_BACKEND;
+        }
+        REFERENCE_BACKEND
     }
 
     /// Compute an MSM using pre-specified generators.
@@ -71,20 +621,77 @@ impl<T: CurveId> MsmHandle<T> {
     ///       .
     ///       .
     ///    res[m-1] = s_m1 * g_1 + s_12 * g_2 + ... + s_mn * g_n
-    pub fn msm(&self, res: &mut [T], element_num_bytes: u32, scalars: &[u8]) {
-        let num_outputs = res.len() as u32;
-        assert!(scalars.len() as u32 % (num_outputs * element_num_bytes) == 0);
-        let n = scalars.len() as u32 / (num_outputs * element_num_bytes);
-        unsafe {
-            blitzar_sys::sxt_fixed_multiexponentiation(
-                res.as_ptr() as *mut std::ffi::c_void,
-                self.handle,
-                element_num_bytes,
-                num_outputs,
-                n,
-                scalars.as_ptr(),
-            );
+    ///
+    /// Returns an error if `element_num_bytes` is not between 1 and 32, if the length of
+    /// `scalars` is not a multiple of `element_num_bytes * m`, or if `n` exceeds the
+    /// number of generators of the handle.
+    pub fn msm(
+        &self,
+        res: &mut [T],
+        element_num_bytes: u32,
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let num_outputs = res.len();
+        let n = count_msm_rows(num_outputs, element_num_bytes, scalars.len())?;
+        self.check_length(n)?;
+        if num_outputs == 0 {
+            return Ok(());
         }
+        match &self.handle {
+            #[cfg(feature = "blitzar-sys")]
+            Handle::Native(handle) => unsafe {
+                blitzar_sys::sxt_fixed_multiexponentiation(
+                    res.as_mut_ptr() as *mut std::ffi::c_void,
+                    *handle,
+                    element_num_bytes,
+                    num_outputs as u32,
+                    n as u32,
+                    scalars.as_ptr(),
+                );
+            },
+            Handle::Reference(generators) => generators.vlen_msm(
+                res,
+                &vec![8 * element_num_bytes; num_outputs],
+                &vec![n as u32; num_outputs],
+                scalars,
+            ),
+        }
+        Ok(())
+    }
+
+    /// Compute an MSM from a row-major matrix of prime field elements.
+    ///
+    /// This is the same as [MsmHandle::msm_with_scalars] for arkworks field
+    /// elements such as `ark_bls12_381::Fr` or `ark_bn254::Fr`.
+    pub fn msm_with_field_elements<F: PrimeField>(
+        &self,
+        res: &mut [T],
+        scalars: &[F],
+    ) -> Result<(), MsmError> {
+        let rows = split_rows(scalars, res.len())?;
+        let element_num_bytes = (F::MODULUS_BIT_SIZE as usize).div_ceil(8);
+        if element_num_bytes > 32 {
+            return Err(MsmError::InvalidScalarSize);
+        }
+        self.check_length(rows.first().map_or(0, |row| row.len()))?;
+        let bytes: Vec<Vec<u8>> = rows
+            .par_iter()
+            .map(|row| {
+                row.iter()
+                    .flat_map(|x| {
+                        let mut bytes = x.into_bigint().to_bytes_le();
+                        bytes.truncate(element_num_bytes);
+                        bytes
+                    })
+                    .collect()
+            })
+            .collect();
+        let rows: Vec<(&[u8], usize)> = bytes
+            .iter()
+            .map(|row| (row.as_slice(), element_num_bytes))
+            .collect();
+        let scalars = transpose_rows(&rows, element_num_bytes);
+        self.msm(res, element_num_bytes as u32, &scalars)
     }
 
     /// Compute an MSM in packed format using pre-specified generators.
@@ -104,19 +711,39 @@ impl<T: CurveId> MsmHandle<T> {
     /// `scalars` specifies a contiguous multi-dimension `num_bytes` by `n` array laid out in
     /// a packed column-major order as specified by output_bit_table. A given row determines the scalar
     /// exponents for generator g_i with the output scalars packed contiguously and padded with zeros.
-    pub fn packed_msm(&self, res: &mut [T], output_bit_table: &[u32], scalars: &[u8]) {
-        let num_outputs = res.len() as u32;
-        let n = count_scalars_per_output(scalars.len(), output_bit_table);
-        unsafe {
-            blitzar_sys::sxt_fixed_packed_multiexponentiation(
-                res.as_ptr() as *mut std::ffi::c_void,
-                self.handle,
-                output_bit_table.as_ptr(),
-                num_outputs,
-                n,
-                scalars.as_ptr(),
-            );
+    ///
+    /// Returns an error if `output_bit_table` does not have an entry of at most 256 bits per
+    /// output, if the length of `scalars` is not a multiple of `num_bytes`, or if `n` exceeds
+    /// the number of generators of the handle.
+    pub fn packed_msm(
+        &self,
+        res: &mut [T],
+        output_bit_table: &[u32],
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let num_outputs = res.len();
+        let n = count_packed_rows(num_outputs, output_bit_table, scalars.len())?;
+        self.check_length(n)?;
+        if num_outputs == 0 {
+            return Ok(());
         }
+        match &self.handle {
+            #[cfg(feature = "blitzar-sys")]
+            Handle::Native(handle) => unsafe {
+                blitzar_sys::sxt_fixed_packed_multiexponentiation(
+                    res.as_mut_ptr() as *mut std::ffi::c_void,
+                    *handle,
+                    output_bit_table.as_ptr(),
+                    num_outputs as u32,
+                    n as u32,
+                    scalars.as_ptr(),
+                );
+            },
+            Handle::Reference(generators) => {
+                generators.vlen_msm(res, output_bit_table, &vec![n as u32; num_outputs], scalars)
+            }
+        }
+        Ok(())
     }
 
     /// Compute a varying lengthing multiexponentiation of scalars in packed format using a handle to
@@ -128,7 +755,8 @@ impl<T: CurveId> MsmHandle<T> {
     /// An entry output_bit_table[output_index] specifies the number of scalar bits used for
     /// output_index and output_lengths[output_index] specifies the length used for output_index.
     ///
-    /// Note: output_lengths must be sorted in ascending order
+    /// Note: output_lengths must be sorted in ascending order, see
+    /// [MsmHandle::unsorted_vlen_msm] for lengths in any order.
     ///
     /// Put
     ///     bit_sum = sum_{output_index} output_bit_table[output_index]
@@ -139,38 +767,410 @@ impl<T: CurveId> MsmHandle<T> {
     /// multi-dimension `num_bytes` by `n` array laid out in a packed column-major order as specified by
     /// output_bit_table. A given row determines the scalar exponents for generator g_i with the output
     /// scalars packed contiguously and padded with zeros.
+    ///
+    /// Returns an error if `output_bit_table` or `output_lengths` do not have an entry per
+    /// output, if `output_lengths` is not sorted, if `n` exceeds the number of generators of
+    /// the handle, or if `scalars` does not hold exactly `num_bytes * n` bytes.
     pub fn vlen_msm(
         &self,
         res: &mut [T],
         output_bit_table: &[u32],
         output_lengths: &[u32],
         scalars: &[u8],
-    ) {
-        let num_outputs = res.len() as u32;
-        assert_eq!(output_bit_table.len(), num_outputs as usize);
-        assert_eq!(output_lengths.len(), num_outputs as usize);
-        unsafe {
-            blitzar_sys::sxt_fixed_vlen_multiexponentiation(
-                res.as_ptr() as *mut std::ffi::c_void,
-                self.handle,
-                output_bit_table.as_ptr(),
-                output_lengths.as_ptr(),
-                num_outputs,
-                scalars.as_ptr(),
-            );
+    ) -> Result<(), MsmError> {
+        let num_outputs = res.len();
+        let n = count_vlen_rows(num_outputs, output_bit_table, output_lengths, scalars.len())?;
+        self.check_length(n)?;
+        if num_outputs == 0 {
+            return Ok(());
+        }
+        match &self.handle {
+            #[cfg(feature = "blitzar-sys")]
+            Handle::Native(handle) => unsafe {
+                blitzar_sys::sxt_fixed_vlen_multiexponentiation(
+                    res.as_mut_ptr() as *mut std::ffi::c_void,
+                    *handle,
+                    output_bit_table.as_ptr(),
+                    output_lengths.as_ptr(),
+                    num_outputs as u32,
+                    scalars.as_ptr(),
+                );
+            },
+            Handle::Reference(generators) => {
+                generators.vlen_msm(res, output_bit_table, output_lengths, scalars)
+            }
+        }
+        Ok(())
+    }
+
+    /// Compute an MSM using the generators `[start, start + n)` of the handle.
+    ///
+    /// This is the same as [MsmHandle::msm], except that the i-th row of `scalars`
+    /// is multiplied by generator g_{start+i} instead of g_i.
+    ///
+    /// A reference handle uses its generators `[start, start + n)` directly. The
+    /// native backend has no generator offset, so for a native handle the scalars
+    /// are copied after `start` rows of zeros: each call allocates and computes an
+    /// MSM over `start + n` rows, an O(start) overhead over [MsmHandle::msm]. For
+    /// windows far from the first generator that are queried repeatedly, a handle
+    /// created from the generators of the window is faster.
+    pub fn msm_range(
+        &self,
+        res: &mut [T],
+        start: usize,
+        element_num_bytes: u32,
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let num_outputs = res.len();
+        let n = count_msm_rows(num_outputs, element_num_bytes, scalars.len())?;
+        match self.range_generators(start, n)? {
+            Some(generators) => {
+                if num_outputs > 0 {
+                    generators.vlen_msm_range(
+                        res,
+                        start,
+                        &vec![8 * element_num_bytes; num_outputs],
+                        &vec![n as u32; num_outputs],
+                        scalars,
+                    );
+                }
+                Ok(())
+            }
+            None => {
+                let row_num_bytes = num_outputs * element_num_bytes as usize;
+                self.msm(
+                    res,
+                    element_num_bytes,
+                    &pad_scalars(start, row_num_bytes, scalars),
+                )
+            }
+        }
+    }
+
+    /// Compute a packed MSM using the generators `[start, start + n)` of the handle.
+    ///
+    /// See [MsmHandle::packed_msm] for the layout and [MsmHandle::msm_range] for the cost.
+    pub fn packed_msm_range(
+        &self,
+        res: &mut [T],
+        start: usize,
+        output_bit_table: &[u32],
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let num_outputs = res.len();
+        let n = count_packed_rows(num_outputs, output_bit_table, scalars.len())?;
+        match self.range_generators(start, n)? {
+            Some(generators) => {
+                if num_outputs > 0 {
+                    generators.vlen_msm_range(
+                        res,
+                        start,
+                        output_bit_table,
+                        &vec![n as u32; num_outputs],
+                        scalars,
+                    );
+                }
+                Ok(())
+            }
+            None => {
+                let row_num_bytes = count_row_bytes(output_bit_table);
+                self.packed_msm(
+                    res,
+                    output_bit_table,
+                    &pad_scalars(start, row_num_bytes, scalars),
+                )
+            }
+        }
+    }
+
+    /// Compute a variable length MSM using the generators `[start, start + n)` of the handle.
+    ///
+    /// `output_lengths` are relative to `start`, so output j uses the generators
+    /// `[start, start + output_lengths[j])`. See [MsmHandle::vlen_msm] for the layout and
+    /// [MsmHandle::msm_range] for the cost.
+    pub fn vlen_msm_range(
+        &self,
+        res: &mut [T],
+        start: usize,
+        output_bit_table: &[u32],
+        output_lengths: &[u32],
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let num_outputs = res.len();
+        let n = count_vlen_rows(num_outputs, output_bit_table, output_lengths, scalars.len())?;
+        match self.range_generators(start, n)? {
+            Some(generators) => {
+                if num_outputs > 0 {
+                    generators.vlen_msm_range(
+                        res,
+                        start,
+                        output_bit_table,
+                        output_lengths,
+                        scalars,
+                    );
+                }
+                Ok(())
+            }
+            None => {
+                // start + n is at most the number of generators, which fits in a u32
+                let output_lengths: Vec<u32> = output_lengths
+                    .iter()
+                    .map(|length| length + start as u32)
+                    .collect();
+                let row_num_bytes = count_row_bytes(output_bit_table);
+                self.vlen_msm(
+                    res,
+                    output_bit_table,
+                    &output_lengths,
+                    &pad_scalars(start, row_num_bytes, scalars),
+                )
+            }
+        }
+    }
+
+    // Checks that the handle has the generators `[start, start + n)`, returning the
+    // generators of a reference handle, which can be used from `start` on.
+    fn range_generators(&self, start: usize, n: usize) -> Result<Option<&MsmGenerators>, MsmError> {
+        self.check_length(start.saturating_add(n))?;
+        match &self.handle {
+            #[cfg(feature = "blitzar-sys")]
+            Handle::Native(_) => Ok(None),
+            Handle::Reference(generators) => Ok(Some(generators)),
         }
     }
 }
 
+impl<T: CurveId + Clone + Default> MsmHandle<T> {
+    /// Compute a variable length MSM whose `output_lengths` can be in any order.
+    ///
+    /// This is the same as [MsmHandle::vlen_msm] without the requirement that
+    /// `output_lengths` is sorted. The outputs are sorted by length, which moves
+    /// their columns in `scalars` and their entries in `output_bit_table`, and the
+    /// results are written to `res` in the original order. When `output_lengths` is
+    /// already sorted, this calls [MsmHandle::vlen_msm] without copying the scalars.
+    pub fn unsorted_vlen_msm(
+        &self,
+        res: &mut [T],
+        output_bit_table: &[u32],
+        output_lengths: &[u32],
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        if output_lengths.windows(2).all(|w| w[0] <= w[1]) {
+            return self.vlen_msm(res, output_bit_table, output_lengths, scalars);
+        }
+        let num_outputs = res.len();
+        check_output_bit_table(output_bit_table, num_outputs)?;
+        check_output_table(output_lengths, num_outputs)?;
+        let n = output_lengths.iter().max().copied().unwrap_or(0) as usize;
+        if scalars.len() != count_row_bytes(output_bit_table) * n {
+            return Err(MsmError::ScalarsLengthMismatch);
+        }
+
+        let mut order: Vec<usize> = (0..num_outputs).collect();
+        order.sort_by_key(|&i| output_lengths[i]);
+        let sorted_bit_table: Vec<u32> = order.iter().map(|&i| output_bit_table[i]).collect();
+        let sorted_lengths: Vec<u32> = order.iter().map(|&i| output_lengths[i]).collect();
+        let sorted_scalars = permute_packed_columns(scalars, output_bit_table, &order);
+
+        let mut sorted_res = vec![T::default(); num_outputs];
+        self.vlen_msm(
+            &mut sorted_res,
+            &sorted_bit_table,
+            &sorted_lengths,
+            &sorted_scalars,
+        )?;
+        for (res_i, output_index) in sorted_res.into_iter().zip(order) {
+            res[output_index] = res_i;
+        }
+        Ok(())
+    }
+}
+
+impl<T> MsmHandle<T>
+where
+    T: CurveId + Clone + Default + for<'a> SubAssign<&'a T>,
+    for<'a> &'a T: Add<&'a T, Output = T>,
+{
+    /// Compute an MSM from a row-major matrix of curve25519 scalars.
+    ///
+    /// Given a matrix with m rows, where `scalars[j * n + i]` is the scalar of generator
+    /// g_{i+1} for output j, `res[j]` will contain the sum of those scalars multiplied
+    /// by their generators. The scalars are transposed into the layout of
+    /// [MsmHandle::msm] internally.
+    ///
+    /// Returns an error if the length of `scalars` is not a multiple of m or if `n`
+    /// exceeds the number of generators of the handle.
+    pub fn msm_with_scalars(&self, res: &mut [T], scalars: &[Scalar]) -> Result<(), MsmError> {
+        let rows = split_rows(scalars, res.len())?;
+        let rows: Vec<Sequence> = rows.into_iter().map(Sequence::from).collect();
+        self.msm_with_sequences(res, &rows)
+    }
+
+    /// Compute an MSM with one sequence of scalars per output.
+    ///
+    /// `res[j]` will contain the sum of the elements of `scalars[j]` multiplied by
+    /// the generators of the handle. Sequences may have different lengths and
+    /// element sizes; shorter sequences are treated as if they were padded with zeros.
+    ///
+    /// Signed sequences are interpreted as two's complement, so negative values give the
+    /// same results as [crate::compute::compute_curve25519_commitments].
+    ///
+    /// Returns an error if the number of sequences does not match the number of outputs
+    /// or if any sequence is longer than the number of generators of the handle.
+    pub fn msm_with_sequences(&self, res: &mut [T], scalars: &[Sequence]) -> Result<(), MsmError> {
+        if scalars.len() != res.len() {
+            return Err(MsmError::OutputTableLengthMismatch {
+                expected: res.len(),
+                found: scalars.len(),
+            });
+        }
+        let n = scalars.iter().map(Sequence::len).max().unwrap_or(0);
+        self.check_length(n)?;
+        let element_num_bytes = scalars
+            .iter()
+            .map(Sequence::element_size)
+            .max()
+            .unwrap_or(1);
+        let rows: Vec<(&[u8], usize)> = scalars
+            .iter()
+            .map(|row| (row.as_bytes(), row.element_size()))
+            .collect();
+        let mut transposed = transpose_rows(&rows, element_num_bytes);
+        let signed: Vec<(usize, u32)> = scalars
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.is_signed())
+            .map(|(j, row)| (j, 8 * row.element_size() as u32))
+            .collect();
+        let sign_bits: Vec<usize> = signed
+            .iter()
+            .map(|(j, bits)| 8 * j * element_num_bytes + *bits as usize - 1)
+            .collect();
+        flip_sign_bits(&mut transposed, res.len() * element_num_bytes, &sign_bits);
+        self.msm(res, element_num_bytes as u32, &transposed)?;
+        self.remove_signed_offsets(res, n, &signed)
+    }
+
+    /// Compute an MSM whose outputs can hold signed scalars.
+    ///
+    /// This is the same as [MsmHandle::msm], except that the scalars of output j are
+    /// read as two's complement `8 * element_num_bytes`-bit integers when
+    /// `output_signs[j]` is true.
+    ///
+    /// Signed scalars are shifted by 2^(w-1) to make them unsigned, and the shift is
+    /// removed from the results with an additional MSM that sums the generators.
+    ///
+    /// Returns the errors of [MsmHandle::msm], or an error if `output_signs` does not
+    /// have an entry per output.
+    pub fn signed_msm(
+        &self,
+        res: &mut [T],
+        element_num_bytes: u32,
+        output_signs: &[bool],
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let num_outputs = res.len();
+        let n = count_msm_rows(num_outputs, element_num_bytes, scalars.len())?;
+        let output_bit_table = vec![8 * element_num_bytes; num_outputs];
+        self.signed_packed_msm_impl(
+            res,
+            n,
+            &output_bit_table,
+            output_signs,
+            scalars,
+            |res, s| self.msm(res, element_num_bytes, s),
+        )
+    }
+
+    /// Compute a packed MSM whose outputs can hold signed scalars.
+    ///
+    /// This is the same as [MsmHandle::packed_msm], except that the
+    /// `output_bit_table[j]` bits of output j are read as a two's complement integer
+    /// when `output_signs[j]` is true. See [MsmHandle::signed_msm] for the cost.
+    ///
+    /// Returns the errors of [MsmHandle::packed_msm], or an error if `output_signs`
+    /// does not have an entry per output.
+    pub fn signed_packed_msm(
+        &self,
+        res: &mut [T],
+        output_bit_table: &[u32],
+        output_signs: &[bool],
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
+        let n = count_packed_rows(res.len(), output_bit_table, scalars.len())?;
+        self.signed_packed_msm_impl(res, n, output_bit_table, output_signs, scalars, |res, s| {
+            self.packed_msm(res, output_bit_table, s)
+        })
+    }
+
+    // Converts the signed outputs of a packed layout to offset encoding, computes
+    // the MSM with `msm`, and removes the offsets from the results.
+    fn signed_packed_msm_impl(
+        &self,
+        res: &mut [T],
+        n: usize,
+        output_bit_table: &[u32],
+        output_signs: &[bool],
+        scalars: &[u8],
+        msm: impl FnOnce(&mut [T], &[u8]) -> Result<(), MsmError>,
+    ) -> Result<(), MsmError> {
+        check_output_table(output_signs, res.len())?;
+        let mut signed = Vec::new();
+        let mut sign_bits = Vec::new();
+        let mut bit_offset = 0;
+        for (j, (bits, is_signed)) in output_bit_table.iter().zip(output_signs).enumerate() {
+            bit_offset += *bits as usize;
+            if *is_signed && *bits > 0 {
+                signed.push((j, *bits));
+                sign_bits.push(bit_offset - 1);
+            }
+        }
+        if signed.is_empty() {
+            return msm(res, scalars);
+        }
+        let mut scalars = scalars.to_vec();
+        flip_sign_bits(&mut scalars, count_row_bytes(output_bit_table), &sign_bits);
+        msm(res, &scalars)?;
+        self.remove_signed_offsets(res, n, &signed)
+    }
+
+    // Removes the offset of the `(output index, bit width)` pairs in `signed`, whose
+    // scalars were shifted for all of the first `n` generators.
+    fn remove_signed_offsets(
+        &self,
+        res: &mut [T],
+        n: usize,
+        signed: &[(usize, u32)],
+    ) -> Result<(), MsmError> {
+        if signed.is_empty() {
+            return Ok(());
+        }
+        let mut sum = [T::default()];
+        self.msm(&mut sum, 1, &vec![1; n])?;
+        for (j, bits) in signed {
+            remove_signed_offset(&mut res[*j], &sum[0], *bits);
+        }
+        Ok(())
+    }
+}
+
 impl<T: CurveId> Drop for MsmHandle<T> {
     fn drop(&mut self) {
-        unsafe {
-            blitzar_sys::sxt_multiexp_handle_free(self.handle);
+        match self.handle {
+            #[cfg(feature = "blitzar-sys")]
+            Handle::Native(handle) => unsafe {
+                blitzar_sys::sxt_multiexp_handle_free(handle);
+            },
+            Handle::Reference(_) => {}
         }
     }
 }
 
 /// Extend MsmHandle to work with affine coordinates for short Weierstrass curve elements
+///
+/// This is implemented for the G1 and G2 groups of BLS12-381 and BN254 and for
+/// Grumpkin. The G2 handles compute MSMs with the reference backend, see
+/// [MsmHandle::new].
 pub trait SwMsmHandle {
     /// Type of an Affine curve element
     type AffineElement;
@@ -179,7 +1179,12 @@ pub trait SwMsmHandle {
     fn new_with_affine(generators: &[Self::AffineElement]) -> Self;
 
     /// Compute a MSM with the result given as affine elements
-    fn affine_msm(&self, res: &mut [Self::AffineElement], element_num_bytes: u32, scalars: &[u8]);
+    fn affine_msm(
+        &self,
+        res: &mut [Self::AffineElement],
+        element_num_bytes: u32,
+        scalars: &[u8],
+    ) -> Result<(), MsmError>;
 
     /// Compute a packed MSM with the result given as affine elements
     fn affine_packed_msm(
@@ -187,7 +1192,7 @@ pub trait SwMsmHandle {
         res: &mut [Self::AffineElement],
         output_bit_table: &[u32],
         scalars: &[u8],
-    );
+    ) -> Result<(), MsmError>;
 
     /// Compute a variable length MSM with the result given as affine elements
     fn affine_vlen_msm(
@@ -196,7 +1201,7 @@ pub trait SwMsmHandle {
         output_bit_table: &[u32],
         output_lengths: &[u32],
         scalars: &[u8],
-    );
+    ) -> Result<(), MsmError>;
 }
 
 impl<C: SwCurveConfig + Clone> SwMsmHandle for MsmHandle<ElementP2<C>> {
@@ -207,12 +1212,16 @@ impl<C: SwCurveConfig + Clone> SwMsmHandle for MsmHandle<ElementP2<C>> {
         MsmHandle::new(&generators)
     }
 
-    fn affine_msm(&self, res: &mut [Self::AffineElement], element_num_bytes: u32, scalars: &[u8]) {
+    fn affine_msm(
+        &self,
+        res: &mut [Self::AffineElement],
+        element_num_bytes: u32,
+        scalars: &[u8],
+    ) -> Result<(), MsmError> {
         let mut res_p: Vec<ElementP2<C>> = vec![ElementP2::<C>::default(); res.len()];
-        self.msm(&mut res_p, element_num_bytes, scalars);
-        res.par_iter_mut().zip(res_p).for_each(|(resi, resi_p)| {
-            *resi = resi_p.into();
-        });
+        self.msm(&mut res_p, element_num_bytes, scalars)?;
+        res.copy_from_slice(&ElementP2::batch_normalize(&res_p));
+        Ok(())
     }
 
     fn affine_packed_msm(
@@ -220,12 +1229,11 @@ impl<C: SwCurveConfig + Clone> SwMsmHandle for MsmHandle<ElementP2<C>> {
         res: &mut [Self::AffineElement],
         output_bit_table: &[u32],
         scalars: &[u8],
-    ) {
+    ) -> Result<(), MsmError> {
         let mut res_p: Vec<ElementP2<C>> = vec![ElementP2::<C>::default(); res.len()];
-        self.packed_msm(&mut res_p, output_bit_table, scalars);
-        res.par_iter_mut().zip(res_p).for_each(|(resi, resi_p)| {
-            *resi = resi_p.into();
-        });
+        self.packed_msm(&mut res_p, output_bit_table, scalars)?;
+        res.copy_from_slice(&ElementP2::batch_normalize(&res_p));
+        Ok(())
     }
 
     fn affine_vlen_msm(
@@ -234,12 +1242,11 @@ impl<C: SwCurveConfig + Clone> SwMsmHandle for MsmHandle<ElementP2<C>> {
         output_bit_table: &[u32],
         output_lengths: &[u32],
         scalars: &[u8],
-    ) {
+    ) -> Result<(), MsmError> {
         let mut res_p: Vec<ElementP2<C>> = vec![ElementP2::<C>::default(); res.len()];
-        self.vlen_msm(&mut res_p, output_bit_table, output_lengths, scalars);
-        res.par_iter_mut().zip(res_p).for_each(|(resi, resi_p)| {
-            *resi = resi_p.into();
-        });
+        self.vlen_msm(&mut res_p, output_bit_table, output_lengths, scalars)?;
+        res.copy_from_slice(&ElementP2::batch_normalize(&res_p));
+        Ok(())
     }
 }
 
@@ -247,19 +1254,81 @@ impl<C: SwCurveConfig + Clone> SwMsmHandle for MsmHandle<ElementP2<C>> {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "blitzar-sys")]
+    #[test]
+    fn a_native_backend_rejects_handle_files_of_the_reference_backend() {
+        use curve25519_dalek::ristretto::RistrettoPoint;
+
+        let generators: Vec<RistrettoPoint> = (0..3u64)
+            .map(|i| RistrettoPoint::mul_base(&Scalar::from(i + 1)))
+            .collect();
+        let handle = MsmHandle::<RistrettoPoint> {
+            handle: Handle::Reference(MsmGenerators::new(&generators)),
+            num_generators: 3,
+            phantom: PhantomData,
+        };
+        let path = std::env::temp_dir().join("blitzar_msm_handle_backend_test.bin");
+        handle.write_to_file(&path).unwrap();
+
+        let loaded = MsmHandle::<RistrettoPoint>::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        if use_reference() {
+            assert_eq!(loaded.unwrap().num_generators(), 3);
+        } else {
+            assert!(matches!(
+                loaded,
+                Err(MsmError::BackendMismatch {
+                    expected: 0,
+                    found: 1
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn we_can_permute_packed_columns() {
+        // columns of 3, 1 and 12 bits holding 5, 1 and 0xabc
+        let scalars = [0b1100_1101, 0b1010_1011];
+        let permuted = permute_packed_columns(&scalars, &[3, 1, 12], &[2, 0, 1]);
+        // 0xabc | 5 << 12 | 1 << 15
+        assert_eq!(permuted, [0xbc, 0xda]);
+
+        // byte-aligned columns are moved per byte
+        let scalars = [1, 2, 3, 4, 5, 6];
+        let permuted = permute_packed_columns(&scalars, &[8, 16], &[1, 0]);
+        assert_eq!(permuted, [2, 3, 1, 5, 6, 4]);
+
+        assert!(permute_packed_columns(&[], &[], &[]).is_empty());
+    }
+
     #[test]
     fn we_can_count_the_number_of_scalars_per_output() {
         let output_bit_table = [1];
-        let n = count_scalars_per_output(1, &output_bit_table);
+        let n = count_scalars_per_output(1, &output_bit_table).unwrap();
         assert_eq!(n, 1);
 
         let output_bit_table = [14, 2];
-        let n = count_scalars_per_output(10, &output_bit_table);
+        let n = count_scalars_per_output(10, &output_bit_table).unwrap();
         assert_eq!(n, 5);
 
         // we handle cases that overflow
         let output_bit_table = [u32::MAX, 1];
-        let n = count_scalars_per_output((u32::MAX as usize) + 1, &output_bit_table);
+        let n = count_scalars_per_output((u32::MAX as usize) + 1, &output_bit_table).unwrap();
         assert_eq!(n, 8);
     }
+
+    #[test]
+    fn we_cannot_count_scalars_that_do_not_fill_whole_rows() {
+        let output_bit_table = [14, 2];
+        assert!(matches!(
+            count_scalars_per_output(9, &output_bit_table),
+            Err(MsmError::ScalarsLengthMismatch)
+        ));
+
+        let output_bit_table = [0];
+        assert!(matches!(
+            count_scalars_per_output(1, &output_bit_table),
+            Err(MsmError::ScalarsLengthMismatch)
+        ));
+    }
 }
@@ -1,8 +1,14 @@
 use super::*;
-use crate::compute::ElementP2;
+use crate::{
+    compute::{compute_curve25519_commitments_with_generators, ElementP2, MsmError},
+    sequence::Sequence,
+};
 use ark_bls12_381::G1Affine;
 use ark_std::UniformRand;
-use curve25519_dalek::ristretto::RistrettoPoint;
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
 use rand_core::OsRng;
 
 #[test]
@@ -20,12 +26,12 @@ fn we_can_compute_msms_using_a_single_generator() {
 
     // 1 * g
     let scalars: Vec<u8> = vec![1];
-    handle.msm(&mut res, 1, &scalars);
+    handle.msm(&mut res, 1, &scalars).unwrap();
     assert_eq!(res[0], generators[0]);
 
     // 2 * g
     let scalars: Vec<u8> = vec![2];
-    handle.msm(&mut res, 1, &scalars);
+    handle.msm(&mut res, 1, &scalars).unwrap();
     assert_eq!(res[0], generators[0] + generators[0]);
 }
 
@@ -44,7 +50,7 @@ fn we_can_compute_msms_using_multiple_generator() {
 
     // g[0] + 2 * g[1]
     let scalars: Vec<u8> = vec![1, 2];
-    handle.msm(&mut res, 1, &scalars);
+    handle.msm(&mut res, 1, &scalars).unwrap();
     assert_eq!(res[0], generators[0] + generators[1] + generators[1]);
 }
 
@@ -64,7 +70,7 @@ fn we_can_compute_msms_using_multiple_outputs() {
     // g[0] + 2 * g[1]
     // 3 * g[0] + g[1]
     let scalars: Vec<u8> = vec![1, 3, 2, 1];
-    handle.msm(&mut res, 1, &scalars);
+    handle.msm(&mut res, 1, &scalars).unwrap();
     assert_eq!(res[0], generators[0] + generators[1] + generators[1]);
     assert_eq!(
         res[1],
@@ -89,7 +95,9 @@ fn we_can_compute_packed_msms() {
     // g[0]
     let output_bit_table: Vec<u32> = vec![3, 1];
     let scalars: Vec<u8> = vec![0b1001, 0b0011];
-    handle.packed_msm(&mut res, &output_bit_table, &scalars);
+    handle
+        .packed_msm(&mut res, &output_bit_table, &scalars)
+        .unwrap();
     assert_eq!(
         res[0],
         generators[0] + generators[1] + generators[1] + generators[1]
@@ -115,11 +123,69 @@ fn we_can_compute_variable_length_msms() {
     let output_bit_table: Vec<u32> = vec![3, 1];
     let output_lengths: Vec<u32> = vec![1, 2];
     let scalars: Vec<u8> = vec![0b1001, 0b1011];
-    handle.vlen_msm(&mut res, &output_bit_table, &output_lengths, &scalars);
+    handle
+        .vlen_msm(&mut res, &output_bit_table, &output_lengths, &scalars)
+        .unwrap();
     assert_eq!(res[0], generators[0]);
     assert_eq!(res[1], generators[0] + generators[1]);
 }
 
+#[test]
+fn we_can_compute_variable_length_msms_with_unsorted_lengths() {
+    let mut rng = OsRng;
+
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    // 5 * g[0] + 2 * g[1] + 7 * g[2]
+    // g[0]
+    // 300 * g[0] + 9 * g[1]
+    let output_bit_table: Vec<u32> = vec![3, 1, 12];
+    let output_lengths: Vec<u32> = vec![3, 1, 2];
+    let row = |a: u32, b: u32, c: u32| {
+        let bits = a | (b << 3) | (c << 4);
+        [bits as u8, (bits >> 8) as u8]
+    };
+    let scalars: Vec<u8> = [row(5, 1, 300), row(2, 0, 9), row(7, 0, 0)].concat();
+
+    let mut res = vec![RistrettoPoint::default(); 3];
+    handle
+        .unsorted_vlen_msm(&mut res, &output_bit_table, &output_lengths, &scalars)
+        .unwrap();
+    let s = Scalar::from;
+    assert_eq!(
+        res[0],
+        s(5_u32) * generators[0] + s(2_u32) * generators[1] + s(7_u32) * generators[2]
+    );
+    assert_eq!(res[1], generators[0]);
+    assert_eq!(
+        res[2],
+        s(300_u32) * generators[0] + s(9_u32) * generators[1]
+    );
+
+    // sorted lengths give the same results as vlen_msm
+    let mut expected = vec![RistrettoPoint::default(); 2];
+    handle
+        .vlen_msm(&mut expected, &[8, 8], &[1, 2], &[1, 3, 2, 4])
+        .unwrap();
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle
+        .unsorted_vlen_msm(&mut res, &[8, 8], &[1, 2], &[1, 3, 2, 4])
+        .unwrap();
+    assert_eq!(res, expected);
+
+    // the scalars must still hold a row per generator of the longest output
+    assert!(matches!(
+        handle.unsorted_vlen_msm(&mut res, &[8, 8], &[2, 1], &[1, 2]),
+        Err(MsmError::ScalarsLengthMismatch)
+    ));
+    assert!(matches!(
+        handle.unsorted_vlen_msm(&mut res, &[8, 8], &[4, 1], &[0; 8]),
+        Err(MsmError::LengthExceedsGenerators { .. })
+    ));
+}
+
 #[test]
 fn we_can_compute_msms_using_a_single_generator_bls12_381() {
     let mut rng = ark_std::test_rng();
@@ -137,7 +203,7 @@ fn we_can_compute_msms_using_a_single_generator_bls12_381() {
 
     // 2 * g
     let scalars: Vec<u8> = vec![2];
-    handle.msm(&mut res, 1, &scalars);
+    handle.msm(&mut res, 1, &scalars).unwrap();
     let r: G1Affine = res[0].clone().into();
     assert_eq!(r, g + g);
 }
@@ -159,14 +225,475 @@ fn for_short_weierstrass_curvs_we_can_compute_msms_with_affine_elements() {
 
     // 2 * g
     let scalars: Vec<u8> = vec![2];
-    handle.affine_msm(&mut res, 1, &scalars);
+    handle.affine_msm(&mut res, 1, &scalars).unwrap();
     assert_eq!(res[0], g + g);
 
     let output_bit_table: Vec<u32> = vec![2];
-    handle.affine_packed_msm(&mut res, &output_bit_table, &scalars);
+    handle
+        .affine_packed_msm(&mut res, &output_bit_table, &scalars)
+        .unwrap();
     assert_eq!(res[0], g + g);
 
     let output_lengths: Vec<u32> = vec![1];
-    handle.affine_vlen_msm(&mut res, &output_bit_table, &output_lengths, &scalars);
+    handle
+        .affine_vlen_msm(&mut res, &output_bit_table, &output_lengths, &scalars)
+        .unwrap();
     assert_eq!(res[0], g + g);
 }
+
+#[test]
+fn we_can_write_a_handle_to_a_file_and_load_it_back() {
+    let mut rng = OsRng;
+
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    let path = std::env::temp_dir().join("blitzar_msm_handle_roundtrip_test.bin");
+    handle.write_to_file(&path).unwrap();
+    let loaded_handle = MsmHandle::<RistrettoPoint>::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // g[0] + 2 * g[1] + 3 * g[2]
+    let scalars: Vec<u8> = vec![1, 2, 3];
+    let mut res = vec![RistrettoPoint::default(); 1];
+    let mut loaded_res = vec![RistrettoPoint::default(); 1];
+    handle.msm(&mut res, 1, &scalars).unwrap();
+    loaded_handle.msm(&mut loaded_res, 1, &scalars).unwrap();
+    assert_eq!(res, loaded_res);
+    assert_eq!(
+        loaded_res[0],
+        generators[0]
+            + generators[1]
+            + generators[1]
+            + generators[2]
+            + generators[2]
+            + generators[2]
+    );
+}
+
+#[test]
+fn we_cannot_load_a_handle_file_that_was_modified_or_written_for_another_curve() {
+    let mut rng = OsRng;
+
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let path = std::env::temp_dir().join("blitzar_msm_handle_invalid_test.bin");
+    handle.write_to_file(&path).unwrap();
+    let contents = std::fs::read(&path).unwrap();
+
+    // the curve is checked
+    assert!(matches!(
+        MsmHandle::<ElementP2<ark_bls12_381::g1::Config>>::from_file(&path),
+        Err(MsmError::CurveMismatch { .. })
+    ));
+
+    // the checksum is checked
+    let mut corrupted = contents.clone();
+    *corrupted.last_mut().unwrap() ^= 1;
+    std::fs::write(&path, &corrupted).unwrap();
+    assert!(matches!(
+        MsmHandle::<RistrettoPoint>::from_file(&path),
+        Err(MsmError::ChecksumMismatch)
+    ));
+
+    // the format version is checked
+    let mut corrupted = contents.clone();
+    corrupted[8] = corrupted[8].wrapping_add(1);
+    std::fs::write(&path, &corrupted).unwrap();
+    assert!(matches!(
+        MsmHandle::<RistrettoPoint>::from_file(&path),
+        Err(MsmError::UnsupportedVersion(_))
+    ));
+
+    // the backend is checked
+    let mut corrupted = contents.clone();
+    corrupted[24] ^= 1;
+    std::fs::write(&path, &corrupted).unwrap();
+    assert!(matches!(
+        MsmHandle::<RistrettoPoint>::from_file(&path),
+        Err(MsmError::BackendMismatch { .. })
+    ));
+
+    // arbitrary files are rejected
+    std::fs::write(&path, b"not a handle").unwrap();
+    assert!(matches!(
+        MsmHandle::<RistrettoPoint>::from_file(&path),
+        Err(MsmError::InvalidFile)
+    ));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn a_handle_remembers_its_generator_count_and_curve() {
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+    assert_eq!(handle.num_generators(), 3);
+    #[cfg(feature = "blitzar-sys")]
+    assert_eq!(handle.curve_id(), blitzar_sys::SXT_CURVE_RISTRETTO255);
+}
+
+#[test]
+fn we_can_compute_msms_with_no_outputs() {
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let mut res: Vec<RistrettoPoint> = vec![];
+    assert!(handle.msm(&mut res, 1, &[]).is_ok());
+    assert!(handle.packed_msm(&mut res, &[], &[]).is_ok());
+    assert!(handle.vlen_msm(&mut res, &[], &[], &[]).is_ok());
+}
+
+#[test]
+fn we_cannot_compute_msms_longer_than_the_handle() {
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..2).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 1];
+
+    assert!(matches!(
+        handle.msm(&mut res, 1, &[1, 2, 3]),
+        Err(MsmError::LengthExceedsGenerators {
+            length: 3,
+            num_generators: 2
+        })
+    ));
+    assert!(matches!(
+        handle.packed_msm(&mut res, &[8], &[1, 2, 3]),
+        Err(MsmError::LengthExceedsGenerators { .. })
+    ));
+    assert!(matches!(
+        handle.vlen_msm(&mut res, &[8], &[3], &[1, 2, 3]),
+        Err(MsmError::LengthExceedsGenerators { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_compute_msms_with_mismatched_buffers() {
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..4).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 2];
+
+    // the scalars must fill whole rows
+    assert!(matches!(
+        handle.msm(&mut res, 1, &[1, 2, 3]),
+        Err(MsmError::ScalarsLengthMismatch)
+    ));
+    assert!(matches!(
+        handle.packed_msm(&mut res, &[8, 8], &[1, 2, 3]),
+        Err(MsmError::ScalarsLengthMismatch)
+    ));
+    assert!(matches!(
+        handle.vlen_msm(&mut res, &[8, 8], &[1, 2], &[1, 2, 3]),
+        Err(MsmError::ScalarsLengthMismatch)
+    ));
+
+    // scalar sizes are bounded
+    assert!(matches!(
+        handle.msm(&mut res, 0, &[]),
+        Err(MsmError::InvalidScalarSize)
+    ));
+    assert!(matches!(
+        handle.msm(&mut res, 33, &[0; 66]),
+        Err(MsmError::InvalidScalarSize)
+    ));
+    assert!(matches!(
+        handle.packed_msm(&mut res, &[257, 8], &[0; 34]),
+        Err(MsmError::InvalidScalarSize)
+    ));
+
+    // per-output tables need an entry per output
+    assert!(matches!(
+        handle.packed_msm(&mut res, &[16], &[1, 2]),
+        Err(MsmError::OutputTableLengthMismatch {
+            expected: 2,
+            found: 1
+        })
+    ));
+    assert!(matches!(
+        handle.vlen_msm(&mut res, &[8, 8], &[2], &[1, 2, 3, 4]),
+        Err(MsmError::OutputTableLengthMismatch { .. })
+    ));
+
+    // output lengths must be sorted
+    assert!(matches!(
+        handle.vlen_msm(&mut res, &[8, 8], &[2, 1], &[1, 2, 3, 4]),
+        Err(MsmError::UnsortedOutputLengths)
+    ));
+}
+
+#[test]
+fn we_can_compute_msms_from_row_major_curve25519_scalars() {
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    // row j holds the scalars of output j
+    let scalars: Vec<Scalar> = (0..6).map(|_| Scalar::random(&mut rng)).collect();
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle.msm_with_scalars(&mut res, &scalars).unwrap();
+    for (j, res_j) in res.iter().enumerate() {
+        let expected: RistrettoPoint = scalars[3 * j..3 * (j + 1)]
+            .iter()
+            .zip(&generators)
+            .map(|(s, g)| s * g)
+            .sum();
+        assert_eq!(*res_j, expected);
+    }
+
+    // the matrix must have the same number of scalars in every row
+    assert!(matches!(
+        handle.msm_with_scalars(&mut res, &scalars[..5]),
+        Err(MsmError::ScalarsLengthMismatch)
+    ));
+}
+
+#[test]
+fn we_can_compute_msms_from_row_major_field_elements() {
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<G1Affine> = (0..3).map(|_| G1Affine::rand(&mut rng)).collect();
+    let handle = MsmHandle::<ElementP2<ark_bls12_381::g1::Config>>::new_with_affine(&generators);
+
+    let scalars: Vec<ark_bls12_381::Fr> =
+        (0..6).map(|_| ark_bls12_381::Fr::rand(&mut rng)).collect();
+    let mut res = vec![ElementP2::<ark_bls12_381::g1::Config>::default(); 2];
+    handle.msm_with_field_elements(&mut res, &scalars).unwrap();
+    for (j, res_j) in res.iter().enumerate() {
+        let expected: ark_bls12_381::G1Projective = scalars[3 * j..3 * (j + 1)]
+            .iter()
+            .zip(&generators)
+            .map(|(s, g)| *g * s)
+            .sum();
+        assert_eq!(G1Affine::from(res_j.clone()), G1Affine::from(expected));
+    }
+}
+
+#[test]
+fn we_can_compute_msms_from_sequences_of_different_lengths_and_sizes() {
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    let row0: &[u8] = &[1, 2, 3];
+    let row1: &[u64] = &[u64::MAX];
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle
+        .msm_with_sequences(&mut res, &[row0.into(), row1.into()])
+        .unwrap();
+    assert_eq!(
+        res[0],
+        generators[0] + Scalar::from(2_u64) * generators[1] + Scalar::from(3_u64) * generators[2]
+    );
+    assert_eq!(res[1], Scalar::from(u64::MAX) * generators[0]);
+
+    // sequences cannot be longer than the handle
+    let row2: &[u8] = &[1, 2, 3, 4];
+    assert!(matches!(
+        handle.msm_with_sequences(&mut res, &[row0.into(), row2.into()]),
+        Err(MsmError::LengthExceedsGenerators { .. })
+    ));
+
+    // there must be one sequence per output
+    assert!(matches!(
+        handle.msm_with_sequences(&mut res, &[row0.into()]),
+        Err(MsmError::OutputTableLengthMismatch { .. })
+    ));
+}
+
+#[test]
+fn we_can_compute_msms_from_signed_sequences() {
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    let row0: &[i32] = &[-1, 2, i32::MIN];
+    let row1: &[i8] = &[-5];
+    let row2: &[u16] = &[7, 8];
+    let rows: Vec<Sequence> = vec![row0.into(), row1.into(), row2.into()];
+    let mut res = vec![RistrettoPoint::default(); 3];
+    handle.msm_with_sequences(&mut res, &rows).unwrap();
+
+    let mut expected = vec![CompressedRistretto::default(); 3];
+    compute_curve25519_commitments_with_generators(&mut expected, &rows, &generators);
+    let res: Vec<CompressedRistretto> = res.iter().map(|r| r.compress()).collect();
+    assert_eq!(res, expected);
+    assert_eq!(res[1], (-Scalar::from(5_u64) * generators[0]).compress());
+}
+
+#[test]
+fn we_can_compute_msms_with_signed_scalars() {
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..2).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+
+    // -2 * g[0] + 3 * g[1]
+    // 65534 * g[0] + 3 * g[1]
+    let scalars: Vec<u8> = [
+        (-2_i16).to_le_bytes(),
+        [0xfe, 0xff],
+        3_i16.to_le_bytes(),
+        [3, 0],
+    ]
+    .concat();
+    let mut res = vec![RistrettoPoint::default(); 2];
+    handle
+        .signed_msm(&mut res, 2, &[true, false], &scalars)
+        .unwrap();
+    assert_eq!(
+        res[0],
+        -Scalar::from(2_u64) * generators[0] + Scalar::from(3_u64) * generators[1]
+    );
+    assert_eq!(
+        res[1],
+        Scalar::from(65534_u64) * generators[0] + Scalar::from(3_u64) * generators[1]
+    );
+
+    // the signedness table needs an entry per output
+    assert!(matches!(
+        handle.signed_msm(&mut res, 2, &[true], &scalars),
+        Err(MsmError::OutputTableLengthMismatch { .. })
+    ));
+}
+
+#[test]
+fn we_can_compute_packed_msms_with_signed_scalars() {
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<G1Affine> = (0..2).map(|_| G1Affine::rand(&mut rng)).collect();
+    let handle = MsmHandle::<ElementP2<ark_bls12_381::g1::Config>>::new_with_affine(&generators);
+
+    // a 3-bit signed output, a 1-bit unsigned output and a 4-bit signed output
+    // -4 * g[0] + 3 * g[1]
+    // g[0]
+    // -1 * g[0] + 7 * g[1]
+    let output_bit_table = [3, 1, 4];
+    let output_signs = [true, false, true];
+    let scalars = [0b1111_1100, 0b0111_0011];
+    let mut res = vec![ElementP2::<ark_bls12_381::g1::Config>::default(); 3];
+    handle
+        .signed_packed_msm(&mut res, &output_bit_table, &output_signs, &scalars)
+        .unwrap();
+
+    let fr = ark_bls12_381::Fr::from;
+    let expected = [
+        generators[1] * fr(3_u64) - generators[0] * fr(4_u64),
+        generators[0].into(),
+        generators[1] * fr(7_u64) - generators[0] * fr(1_u64),
+    ];
+    for (res_j, expected_j) in res.iter().zip(expected) {
+        assert_eq!(*res_j, ElementP2::from(expected_j));
+    }
+}
+
+#[test]
+fn we_can_compute_msms_over_a_range_of_generators() {
+    let mut rng = OsRng;
+    let generators: Vec<RistrettoPoint> =
+        (0..5).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let mut res = vec![RistrettoPoint::default(); 2];
+
+    // 1 * g[2] + 2 * g[3]
+    // 3 * g[2] + 4 * g[3]
+    handle.msm_range(&mut res, 2, 1, &[1, 3, 2, 4]).unwrap();
+    assert_eq!(res[0], generators[2] + Scalar::from(2_u64) * generators[3]);
+    assert_eq!(
+        res[1],
+        Scalar::from(3_u64) * generators[2] + Scalar::from(4_u64) * generators[3]
+    );
+
+    // the packed variant agrees
+    let mut packed_res = vec![RistrettoPoint::default(); 2];
+    handle
+        .packed_msm_range(&mut packed_res, 2, &[8, 8], &[1, 3, 2, 4])
+        .unwrap();
+    assert_eq!(packed_res, res);
+
+    // output lengths of the vlen variant are relative to the start
+    handle
+        .vlen_msm_range(&mut res, 3, &[8, 8], &[1, 2], &[1, 3, 2, 4])
+        .unwrap();
+    assert_eq!(res[0], generators[3]);
+    assert_eq!(
+        res[1],
+        Scalar::from(3_u64) * generators[3] + Scalar::from(4_u64) * generators[4]
+    );
+
+    // the window must fit in the handle
+    assert!(matches!(
+        handle.msm_range(&mut res, 4, 1, &[1, 3, 2, 4]),
+        Err(MsmError::LengthExceedsGenerators {
+            length: 6,
+            num_generators: 5
+        })
+    ));
+    assert!(matches!(
+        handle.vlen_msm_range(&mut res, 4, &[8, 8], &[1, 2], &[1, 3, 2, 4]),
+        Err(MsmError::LengthExceedsGenerators { .. })
+    ));
+    assert!(matches!(
+        handle.vlen_msm_range(&mut res, usize::MAX, &[8, 8], &[1, 2], &[1, 3, 2, 4]),
+        Err(MsmError::LengthExceedsGenerators { .. })
+    ));
+}
+
+fn we_can_compute_g2_msms_with_a_handle<C: SwCurveConfig + Clone>() {
+    use ark_ec::{
+        short_weierstrass::{Affine, Projective},
+        CurveGroup, VariableBaseMSM,
+    };
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<Affine<C>> = (0..4)
+        .map(|_| Projective::<C>::rand(&mut rng).into_affine())
+        .collect();
+    let handle = MsmHandle::<ElementP2<C>>::new_with_affine(&generators);
+
+    // field element scalars agree with arkworks
+    let scalars: Vec<C::ScalarField> = (0..8).map(|_| C::ScalarField::rand(&mut rng)).collect();
+    let mut res = vec![ElementP2::<C>::default(); 2];
+    handle.msm_with_field_elements(&mut res, &scalars).unwrap();
+    for (j, res_j) in res.iter().enumerate() {
+        let expected = Projective::<C>::msm(&generators, &scalars[4 * j..4 * (j + 1)]).unwrap();
+        assert_eq!(Affine::<C>::from(res_j.clone()), expected.into_affine());
+    }
+
+    // g[0] + 2 * g[1] + 3 * g[2]
+    let mut affine_res = vec![Affine::<C>::default(); 1];
+    handle.affine_msm(&mut affine_res, 1, &[1, 2, 3]).unwrap();
+    let small_scalars: Vec<C::ScalarField> = [1_u64, 2, 3].map(C::ScalarField::from).to_vec();
+    let expected = Projective::<C>::msm(&generators[..3], &small_scalars).unwrap();
+    assert_eq!(affine_res[0], expected.into_affine());
+
+    // the handle can be written to a file and loaded back
+    let path = std::env::temp_dir().join(format!("blitzar_g2_msm_handle_test_{}.bin", C::CURVE_ID));
+    handle.write_to_file(&path).unwrap();
+    let loaded_handle = MsmHandle::<ElementP2<C>>::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut loaded_res = vec![Affine::<C>::default(); 1];
+    loaded_handle
+        .affine_msm(&mut loaded_res, 1, &[1, 2, 3])
+        .unwrap();
+    assert_eq!(loaded_res, affine_res);
+}
+
+#[test]
+fn we_can_compute_bls12_381_g2_msms_with_a_handle() {
+    we_can_compute_g2_msms_with_a_handle::<ark_bls12_381::g2::Config>();
+}
+
+#[test]
+fn we_can_compute_bn254_g2_msms_with_a_handle() {
+    we_can_compute_g2_msms_with_a_handle::<ark_bn254::g2::Config>();
+}
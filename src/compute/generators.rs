@@ -11,33 +11,77 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-use super::backend::init_backend;
-use curve25519_dalek::ristretto::RistrettoPoint;
+#[cfg(feature = "blitzar-sys")]
+use super::backend::use_reference;
+use curve25519_dalek::{ristretto::RistrettoPoint, traits::Identity};
+use rayon::prelude::*;
+use sha3::{Digest, Sha3_512};
+#[cfg(feature = "blitzar-sys")]
 use std::mem::MaybeUninit;
 
 #[doc = include_str!("../../docs/commitments/get_curve25519_generators.md")]
 ///
 /// # Example - Getting the Generators used in the `compute_curve25519_commitments` function
-/// ```no_run
+#[cfg_attr(
+    any(feature = "blitzar-sys", feature = "reference"),
+    doc = " ```no_run"
+)]
+#[cfg_attr(
+    not(any(feature = "blitzar-sys", feature = "reference")),
+    doc = " ```ignore"
+)]
 #[doc = include_str!("../../examples/get_generators.rs")]
 /// ```
 pub fn get_curve25519_generators(generators: &mut [RistrettoPoint], offset_generators: u64) {
-    init_backend();
-
-    unsafe {
-        let sxt_ristretto_generators =
-            generators.as_mut_ptr() as *mut blitzar_sys::sxt_ristretto255;
+    #[cfg(feature = "blitzar-sys")]
+    if !use_reference() {
+        unsafe {
+            let sxt_ristretto_generators =
+                generators.as_mut_ptr() as *mut blitzar_sys::sxt_ristretto255;
 
-        let ret_get_generators = blitzar_sys::sxt_ristretto255_get_generators(
-            sxt_ristretto_generators,
-            generators.len() as u64,
-            offset_generators,
-        );
+            let ret_get_generators = blitzar_sys::sxt_ristretto255_get_generators(
+                sxt_ristretto_generators,
+                generators.len() as u64,
+                offset_generators,
+            );
 
-        if ret_get_generators != 0 {
-            panic!("Error during get_curve25519_generators call");
+            if ret_get_generators != 0 {
+                panic!("Error during get_curve25519_generators call");
+            }
         }
+        return;
     }
+
+    derive_curve25519_generators(generators, offset_generators);
+}
+
+#[doc = include_str!("../../docs/commitments/derive_curve25519_generator.md")]
+///
+/// # Example - Deriving Generators without the Backend
+/// ```no_run
+#[doc = include_str!("../../examples/derive_generators.rs")]
+/// ```
+pub fn derive_curve25519_generator(index: u64) -> RistrettoPoint {
+    let mut rng = GeneratorRng([index.wrapping_add(1), index.wrapping_add(2)]);
+    let mut bytes = [0_u8; 64];
+    for chunk in bytes.chunks_exact_mut(8) {
+        chunk.copy_from_slice(&rng.next_u64().to_le_bytes());
+    }
+    RistrettoPoint::from_uniform_bytes(&bytes)
+}
+
+/// Derives the generators of [get_curve25519_generators] in pure Rust.
+///
+/// `generators[i]` is set to [derive_curve25519_generator] of `offset_generators + i`
+/// (wrapping on overflow),
+/// so this computes the same points as [get_curve25519_generators] without the backend.
+pub fn derive_curve25519_generators(generators: &mut [RistrettoPoint], offset_generators: u64) {
+    generators
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(i, generator)| {
+            *generator = derive_curve25519_generator(offset_generators.wrapping_add(i as u64));
+        });
 }
 
 #[doc = include_str!("../../docs/commitments/get_one_curve25519_commit.md")]
@@ -47,18 +91,106 @@ pub fn get_curve25519_generators(generators: &mut [RistrettoPoint], offset_gener
 #[doc = include_str!("../../examples/get_one_commit.rs")]
 /// ```
 pub fn get_one_curve25519_commit(n: u64) -> RistrettoPoint {
-    init_backend();
+    #[cfg(feature = "blitzar-sys")]
+    if !use_reference() {
+        unsafe {
+            let mut one_commit: MaybeUninit<RistrettoPoint> = MaybeUninit::uninit();
+            let one_commit_ptr = one_commit.as_mut_ptr() as *mut blitzar_sys::sxt_ristretto255;
 
-    unsafe {
-        let mut one_commit: MaybeUninit<RistrettoPoint> = MaybeUninit::uninit();
-        let one_commit_ptr = one_commit.as_mut_ptr() as *mut blitzar_sys::sxt_ristretto255;
+            let ret_get_one_commit = blitzar_sys::sxt_curve25519_get_one_commit(one_commit_ptr, n);
 
-        let ret_get_one_commit = blitzar_sys::sxt_curve25519_get_one_commit(one_commit_ptr, n);
+            if ret_get_one_commit != 0 {
+                panic!("Error during get_one_curve25519_commit call");
+            }
 
-        if ret_get_one_commit != 0 {
-            panic!("Error during get_one_curve25519_commit call");
+            return one_commit.assume_init();
         }
+    }
+
+    derive_one_curve25519_commit(n)
+}
 
-        one_commit.assume_init()
+/// Derives the generator at `index` of the generator set identified by `label`.
+///
+/// Generator sets with different labels are independent of each other and of the
+/// generators of [get_curve25519_generators], so protocols that must not share bases
+/// can each use their own label. The generator is the Ristretto hash-to-group map of
+/// the SHA3-512 hash of:
+///
+/// ```text
+/// b"blitzar labeled generator" || (label.len() as u64).to_le_bytes() || label || index.to_le_bytes()
+/// ```
+///
+/// which is `RistrettoPoint::hash_from_bytes::<Sha3_512>` of the same bytes.
+pub fn derive_labeled_curve25519_generator(label: &[u8], index: u64) -> RistrettoPoint {
+    let hash = Sha3_512::new()
+        .chain_update(LABELED_GENERATOR_DOMAIN)
+        .chain_update((label.len() as u64).to_le_bytes())
+        .chain_update(label)
+        .chain_update(index.to_le_bytes())
+        .finalize();
+    let mut bytes = [0_u8; 64];
+    bytes.copy_from_slice(&hash);
+    RistrettoPoint::from_uniform_bytes(&bytes)
+}
+
+/// Derives the generators of the generator set identified by `label`.
+///
+/// `generators[i]` is set to [derive_labeled_curve25519_generator] of `label` and
+/// `offset_generators + i` (wrapping on overflow). The generators can be passed to
+/// [crate::compute::compute_curve25519_commitments_with_generators],
+/// [crate::compute::MsmHandle::new] and
+/// [crate::proof::InnerProductProof::create_with_generators].
+///
+/// # Example - Committing with a Labeled Generator Set
+#[cfg_attr(
+    any(feature = "blitzar-sys", feature = "reference"),
+    doc = " ```no_run"
+)]
+#[cfg_attr(
+    not(any(feature = "blitzar-sys", feature = "reference")),
+    doc = " ```ignore"
+)]
+#[doc = include_str!("../../examples/labeled_generators.rs")]
+/// ```
+pub fn derive_labeled_curve25519_generators(
+    label: &[u8],
+    generators: &mut [RistrettoPoint],
+    offset_generators: u64,
+) {
+    generators
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(i, generator)| {
+            *generator = derive_labeled_curve25519_generator(
+                label,
+                offset_generators.wrapping_add(i as u64),
+            );
+        });
+}
+
+// separates labeled generators from other uses of SHA3-512
+const LABELED_GENERATOR_DOMAIN: &[u8] = b"blitzar labeled generator";
+
+// xorshift128+ generator used to derive the curve25519 generators, see
+// `derive_curve25519_generator`
+struct GeneratorRng([u64; 2]);
+
+impl GeneratorRng {
+    fn next_u64(&mut self) -> u64 {
+        let [mut t, s] = self.0;
+        t ^= t << 23;
+        t ^= t >> 17;
+        t ^= s ^ (s >> 26);
+        self.0 = [s, t];
+        t.wrapping_add(s)
     }
 }
+
+// Derives the sum of the first `n` generators without the backend.
+pub(crate) fn derive_one_curve25519_commit(n: u64) -> RistrettoPoint {
+    (0..n)
+        .into_par_iter()
+        .map(derive_curve25519_generator)
+        .reduce(RistrettoPoint::identity, |a, b| a + b)
+}
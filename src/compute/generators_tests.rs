@@ -13,13 +13,12 @@
 // limitations under the License.
 
 use super::*;
-use curve25519_dalek::{
-    ristretto::{CompressedRistretto, RistrettoPoint},
-    scalar::Scalar,
-    traits::Identity,
-};
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+use curve25519_dalek::{ristretto::CompressedRistretto, scalar::Scalar};
+use curve25519_dalek::{ristretto::RistrettoPoint, traits::Identity};
 
 #[test]
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
 fn get_generators_is_the_same_used_in_commitment_computation() {
     // generate input table
     let offset_generators = 0_u64;
@@ -51,6 +50,7 @@ fn get_generators_is_the_same_used_in_commitment_computation() {
 }
 
 #[test]
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
 fn get_generators_with_offset_is_the_same_used_in_commitment_computation() {
     // generate input table
     let data: Vec<u32> = vec![0, 0, 0, 0, 4, 7, 6, 8, 9, 10, 0, 0, 0];
@@ -85,3 +85,167 @@ fn get_one_commit_is_valid() {
     assert_eq!(get_one_curve25519_commit(1), generators[0]);
     assert_eq!(get_one_curve25519_commit(2), generators[0] + generators[1]);
 }
+
+// Known-answer vectors of the compressed generators at the given indices.
+//
+// These must be the output of the native backend (`sxt_ristretto255_get_generators`),
+// not of `derive_curve25519_generator`. `generator_vectors_match_the_backend` checks
+// them against blitzar-sys in the cpu/gpu test jobs; if it fails, regenerate the
+// table from the backend rather than from the Rust derivation.
+const GENERATOR_VECTORS: [(u64, &str); 6] = [
+    (
+        0,
+        "e2a9c70ab120176fb996d6f72725b20a1f923259ea1df4c85ebb315b4821335c",
+    ),
+    (
+        1,
+        "4c03b9d441b6b2ba380218adb4c8098ddc5675e2aa2bc3b6342f569051d1f531",
+    ),
+    (
+        2,
+        "7a43898974195a6eed24d38253e0fb99a1491292207cb0f4e78ae6dc9f6d1e33",
+    ),
+    (
+        1000,
+        "b428e4a9a93ae3fb23e068681b2111e55610b6a176474c2065cfd102ceebc03f",
+    ),
+    (
+        1 << 40,
+        "40b3c6584c04740d82fb57387447d15979eed90dbe1565d15c48dfa2ddbef052",
+    ),
+    (
+        u64::MAX,
+        "be7d6a99836eb16e4884a0663d64c5155bfe43c2cfed08bc7eacadec6ef63e79",
+    ),
+];
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[test]
+fn derived_generators_match_the_known_answer_vectors() {
+    for (index, expected) in GENERATOR_VECTORS {
+        let generator = derive_curve25519_generator(index).compress();
+        assert_eq!(to_hex(generator.as_bytes()), expected, "index {index}");
+    }
+}
+
+// With blitzar-sys, `get_curve25519_generators` calls `sxt_ristretto255_get_generators`;
+// these tests are only meaningful when the native backend is selected.
+#[test]
+#[cfg(feature = "blitzar-sys")]
+fn generator_vectors_match_the_backend() {
+    for (index, expected) in GENERATOR_VECTORS {
+        let mut generators = vec![RistrettoPoint::identity(); 1];
+        get_curve25519_generators(&mut generators, index);
+        assert_eq!(
+            to_hex(generators[0].compress().as_bytes()),
+            expected,
+            "index {index}"
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "blitzar-sys")]
+fn derived_generators_match_the_backend_generators() {
+    for offset_generators in [0, 1, 1000, 1 << 40] {
+        let mut expected = vec![RistrettoPoint::identity(); 33];
+        get_curve25519_generators(&mut expected, offset_generators);
+        let mut generators = vec![RistrettoPoint::identity(); 33];
+        derive_curve25519_generators(&mut generators, offset_generators);
+        assert_eq!(generators, expected);
+    }
+
+    let mut expected = vec![RistrettoPoint::identity(); 1];
+    get_curve25519_generators(&mut expected, 1000);
+    assert_eq!(derive_curve25519_generator(1000), expected[0]);
+}
+
+#[test]
+fn derived_generators_are_indexed_by_offset() {
+    let mut generators = vec![RistrettoPoint::identity(); 4];
+    derive_curve25519_generators(&mut generators, 7);
+    for (i, generator) in generators.iter().enumerate() {
+        assert_eq!(*generator, derive_curve25519_generator(7 + i as u64));
+    }
+    assert_ne!(generators[0], generators[1]);
+}
+
+#[test]
+fn labeled_generators_are_indexed_by_offset() {
+    let mut generators = vec![RistrettoPoint::identity(); 4];
+    derive_labeled_curve25519_generators(b"test", &mut generators, 5);
+    for (i, generator) in generators.iter().enumerate() {
+        assert_eq!(
+            *generator,
+            derive_labeled_curve25519_generator(b"test", 5 + i as u64)
+        );
+    }
+    assert_ne!(generators[0], generators[1]);
+}
+
+#[test]
+fn labeled_generators_are_separated_by_label() {
+    let generator = derive_labeled_curve25519_generator(b"a", 0);
+    assert_ne!(generator, derive_labeled_curve25519_generator(b"b", 0));
+    assert_ne!(generator, derive_labeled_curve25519_generator(b"", 0));
+    assert_ne!(generator, derive_curve25519_generator(0));
+
+    // the label length is hashed, so labels cannot run into the index
+    assert_ne!(
+        derive_labeled_curve25519_generator(b"a\x01", 0),
+        derive_labeled_curve25519_generator(b"a", 1)
+    );
+}
+
+#[test]
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+fn labeled_generators_can_be_used_in_commitments_and_msms() {
+    let data: Vec<u32> = vec![4, 7, 6, 8, 9];
+    let mut generators = vec![RistrettoPoint::identity(); data.len()];
+    derive_labeled_curve25519_generators(b"test", &mut generators, 0);
+
+    let mut commitments = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments_with_generators(
+        &mut commitments,
+        &[(&data).into()],
+        &generators,
+    );
+
+    let handle = MsmHandle::new(&generators);
+    let scalars: Vec<u8> = data.iter().flat_map(|x| x.to_le_bytes()).collect();
+    let mut res = vec![RistrettoPoint::identity(); 1];
+    handle.msm(&mut res, 4, &scalars).unwrap();
+
+    let expected = data
+        .iter()
+        .zip(&generators)
+        .map(|(x, g)| Scalar::from(*x) * g)
+        .sum::<RistrettoPoint>();
+    assert_eq!(commitments[0], expected.compress());
+    assert_eq!(res[0], expected);
+}
+
+#[test]
+fn derived_generators_wrap_around_at_the_largest_index() {
+    let mut generators = vec![RistrettoPoint::identity(); 2];
+    derive_curve25519_generators(&mut generators, u64::MAX);
+    assert_eq!(generators[0], derive_curve25519_generator(u64::MAX));
+    assert_eq!(generators[1], derive_curve25519_generator(0));
+}
+
+#[test]
+fn labeled_generators_wrap_around_at_the_largest_index() {
+    let mut generators = vec![RistrettoPoint::identity(); 2];
+    derive_labeled_curve25519_generators(b"test", &mut generators, u64::MAX);
+    assert_eq!(
+        generators[0],
+        derive_labeled_curve25519_generator(b"test", u64::MAX)
+    );
+    assert_eq!(
+        generators[1],
+        derive_labeled_curve25519_generator(b"test", 0)
+    );
+}
@@ -0,0 +1,102 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use ark_ec::{
+    models::CurveConfig,
+    short_weierstrass::{Affine, Projective, SWCurveConfig},
+};
+use ark_ff::{Field, MontFp, PrimeField};
+use rayon::prelude::*;
+use sha3::{Digest, Sha3_512};
+
+/// Affine point on the Grumpkin curve.
+pub type GrumpkinAffine = Affine<GrumpkinConfig>;
+
+/// Projective point on the Grumpkin curve.
+pub type GrumpkinProjective = Projective<GrumpkinConfig>;
+
+/// Parameters of the Grumpkin curve.
+///
+/// Grumpkin is the short Weierstrass curve y^2 = x^3 - 17 over the scalar field
+/// of BN254. Its scalar field is the base field of BN254, so the two curves form
+/// a cycle: BN254 G1 points can be verified in Grumpkin circuits and vice versa.
+///
+/// The curve has prime order (cofactor 1) and uses the generator (1, sqrt(-16)).
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct GrumpkinConfig;
+
+impl CurveConfig for GrumpkinConfig {
+    type BaseField = ark_bn254::Fr;
+    type ScalarField = ark_bn254::Fq;
+
+    const COFACTOR: &'static [u64] = &[1];
+
+    const COFACTOR_INV: ark_bn254::Fq = ark_bn254::Fq::ONE;
+}
+
+impl SWCurveConfig for GrumpkinConfig {
+    const COEFF_A: ark_bn254::Fr = ark_bn254::Fr::ZERO;
+
+    const COEFF_B: ark_bn254::Fr = MontFp!("-17");
+
+    const GENERATOR: GrumpkinAffine = GrumpkinAffine::new_unchecked(
+        MontFp!("1"),
+        MontFp!("17631683881184975370165255887551781615748388533673675138860"),
+    );
+
+    #[inline(always)]
+    fn mul_by_a(_: Self::BaseField) -> Self::BaseField {
+        Self::BaseField::ZERO
+    }
+}
+
+/// Gets the Grumpkin generators `[offset_generators, offset_generators + generators.len())`
+/// (wrapping on overflow), for [crate::compute::compute_grumpkin_uncompressed_commitments_with_generators].
+///
+/// The backend has no Grumpkin generators of its own, so they are derived in pure
+/// Rust and do not depend on the backend. Generator `index` is the point with the
+/// smaller of the two `y` coordinates for the first `x` on the curve, where
+/// `x` is the SHA3-512 hash, reduced modulo the base field, of:
+///
+/// ```text
+/// b"blitzar grumpkin generator" || index.to_le_bytes() || counter.to_le_bytes()
+/// ```
+///
+/// for `counter = 0, 1, ...`. The curve has prime order, so every such point is a
+/// generator of the group, and no discrete log relation between them is known.
+pub fn get_grumpkin_generators(generators: &mut [GrumpkinAffine], offset_generators: u64) {
+    generators
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(i, generator)| {
+            *generator = derive_grumpkin_generator(offset_generators.wrapping_add(i as u64));
+        });
+}
+
+// Derives the Grumpkin generator at `index`, see `get_grumpkin_generators`.
+fn derive_grumpkin_generator(index: u64) -> GrumpkinAffine {
+    (0_u64..)
+        .find_map(|counter| {
+            let hash = Sha3_512::new()
+                .chain_update(GRUMPKIN_GENERATOR_DOMAIN)
+                .chain_update(index.to_le_bytes())
+                .chain_update(counter.to_le_bytes())
+                .finalize();
+            let x = ark_bn254::Fr::from_le_bytes_mod_order(&hash);
+            GrumpkinAffine::get_point_from_x_unchecked(x, false)
+        })
+        .expect("about half of the x coordinates are on the curve")
+}
+
+// Domain separator of the Grumpkin generators.
+const GRUMPKIN_GENERATOR_DOMAIN: &[u8] = b"blitzar grumpkin generator";
@@ -0,0 +1,103 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use ark_std::{UniformRand, Zero};
+
+#[test]
+fn the_grumpkin_generator_is_on_the_curve_and_has_prime_order() {
+    let g = GrumpkinAffine::generator();
+    assert!(g.is_on_curve());
+    assert!(g.is_in_correct_subgroup_assuming_on_curve());
+
+    // the group order is the BN254 base field modulus
+    assert!(g.mul_bigint(ark_bn254::Fq::MODULUS).is_zero());
+    assert!(!g.mul_bigint([1_u64]).is_zero());
+}
+
+#[test]
+fn grumpkin_scalars_are_bn254_base_field_elements_and_vice_versa() {
+    assert_eq!(
+        <ark_bn254::Fr as PrimeField>::MODULUS,
+        <<GrumpkinConfig as ark_ec::CurveConfig>::BaseField as PrimeField>::MODULUS
+    );
+    assert_eq!(
+        <ark_bn254::Fq as PrimeField>::MODULUS,
+        <<GrumpkinConfig as ark_ec::CurveConfig>::ScalarField as PrimeField>::MODULUS
+    );
+}
+
+#[test]
+fn we_can_compute_grumpkin_msms_with_a_handle() {
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<GrumpkinAffine> = (0..4).map(|_| GrumpkinAffine::rand(&mut rng)).collect();
+    let scalars: Vec<ark_bn254::Fq> = (0..4).map(|_| ark_bn254::Fq::rand(&mut rng)).collect();
+
+    let handle = MsmHandle::<ElementP2<GrumpkinConfig>>::new_with_affine(&generators);
+    let mut res = vec![ElementP2::<GrumpkinConfig>::default(); 1];
+    handle.msm_with_field_elements(&mut res, &scalars).unwrap();
+
+    let expected = GrumpkinProjective::msm(&generators, &scalars).unwrap();
+    assert_eq!(GrumpkinAffine::from(&res[0]), expected.into_affine());
+}
+
+#[test]
+fn we_can_get_grumpkin_generators() {
+    let mut generators = vec![GrumpkinAffine::default(); 4];
+    get_grumpkin_generators(&mut generators, 0);
+    for (i, generator) in generators.iter().enumerate() {
+        assert!(generator.is_on_curve());
+        assert!(!generator.is_zero());
+        assert!(!generators[..i].contains(generator));
+    }
+
+    // the offset selects a window of the same generators, wrapping on overflow
+    let mut window = vec![GrumpkinAffine::default(); 2];
+    get_grumpkin_generators(&mut window, 2);
+    assert_eq!(window, generators[2..]);
+    get_grumpkin_generators(&mut window, u64::MAX);
+    assert_eq!(window[1], generators[0]);
+
+    // the generators can be passed to the commitment computation
+    let scalars: Vec<u64> = vec![1, 2, 3, 4];
+    let mut commitments = vec![GrumpkinAffine::default(); 1];
+    compute_grumpkin_uncompressed_commitments_with_generators(
+        &mut commitments,
+        &[(&scalars).into()],
+        &generators,
+    );
+    let scalars: Vec<ark_bn254::Fq> = scalars.into_iter().map(ark_bn254::Fq::from).collect();
+    let expected = GrumpkinProjective::msm(&generators, &scalars).unwrap();
+    assert_eq!(commitments[0], expected.into_affine());
+}
+
+#[test]
+fn grumpkin_generators_match_the_known_answer_vectors() {
+    // the compressed points: the little-endian x coordinate, whose top bits are
+    // zero because the smaller y coordinate is chosen
+    let vectors = [
+        "ef8f79d42c93fa4ecd1a5031f44ac689f377346f050989cd13841516e4ac1427",
+        "226b8c7cafe374801e3609f02eac2d616497c97d9d17ba8f1d9ace706a231b1d",
+    ];
+    let mut generators = vec![GrumpkinAffine::default(); vectors.len()];
+    get_grumpkin_generators(&mut generators, 0);
+    for (generator, expected) in generators.iter().zip(vectors) {
+        let mut bytes = Vec::new();
+        generator.serialize_compressed(&mut bytes).unwrap();
+        let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        assert_eq!(hex, expected);
+    }
+}
@@ -14,34 +14,125 @@
 
 //! commitment and generator computation
 
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
 mod backend;
+#[cfg(feature = "reference")]
+pub use backend::init_reference_backend;
+#[cfg(feature = "blitzar-sys")]
+pub(crate) use backend::use_reference;
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
 pub use backend::{init_backend, init_backend_with_config, BackendConfig};
 
+mod error;
+pub use error::{CommitmentError, MsmError};
+
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
 mod curve;
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
 use curve::CurveId;
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+pub(crate) use curve::SwCurveConfig;
 
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
 mod commitments;
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
 pub use commitments::{
     compute_bls12_381_g1_commitments_with_generators,
     compute_bn254_g1_uncompressed_commitments_with_generators, compute_curve25519_commitments,
-    compute_curve25519_commitments_with_generators, update_curve25519_commitments,
+    compute_curve25519_commitments_with_generators,
+    compute_grumpkin_uncompressed_commitments_with_generators, update_curve25519_commitments,
+    update_curve25519_decompressed_commitments, verify_commitments,
 };
 
-#[cfg(test)]
+#[cfg(all(test, any(feature = "blitzar-sys", feature = "reference")))]
 mod commitments_tests;
 
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+mod row_updates;
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+pub use row_updates::{
+    delete_bls12_381_g1_commitment_rows, delete_bn254_g1_uncompressed_commitment_rows,
+    delete_curve25519_commitment_rows, delete_grumpkin_uncompressed_commitment_rows,
+    modify_bls12_381_g1_commitment_rows, modify_bn254_g1_uncompressed_commitment_rows,
+    modify_curve25519_commitment_rows, modify_grumpkin_uncompressed_commitment_rows,
+};
+#[cfg(all(test, any(feature = "blitzar-sys", feature = "reference")))]
+mod row_updates_tests;
+
+mod commitment_ops;
+pub use commitment_ops::{
+    add_commitments, linear_combination_of_commitments, neg_commitments, scale_commitments,
+    sub_commitments,
+};
+#[cfg(test)]
+mod commitment_ops_tests;
+
+mod compressed_commitment;
+pub use compressed_commitment::{CompressedBls12381G1Commitment, CompressedBn254G1Commitment};
+#[cfg(test)]
+mod compressed_commitment_tests;
+
 mod element_p2;
 pub use element_p2::ElementP2;
 #[cfg(test)]
 mod element_p2_test;
 
+mod grumpkin;
+pub use grumpkin::{get_grumpkin_generators, GrumpkinAffine, GrumpkinConfig, GrumpkinProjective};
+#[cfg(all(test, any(feature = "blitzar-sys", feature = "reference")))]
+mod grumpkin_tests;
+
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
 mod fixed_msm;
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
 pub use fixed_msm::{MsmHandle, SwMsmHandle};
-#[cfg(test)]
+#[cfg(all(test, any(feature = "blitzar-sys", feature = "reference")))]
 mod fixed_msm_tests;
 
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+mod packed_scalars;
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+pub use packed_scalars::PackedScalars;
+#[cfg(all(test, any(feature = "blitzar-sys", feature = "reference")))]
+mod packed_scalars_tests;
+
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+mod extendable_msm;
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+pub use extendable_msm::ExtendableMsmHandle;
+#[cfg(all(test, any(feature = "blitzar-sys", feature = "reference")))]
+mod extendable_msm_tests;
+
+#[cfg(all(
+    feature = "arkworks",
+    any(feature = "blitzar-sys", feature = "reference")
+))]
+mod arkworks_msm;
+#[cfg(all(
+    feature = "arkworks",
+    any(feature = "blitzar-sys", feature = "reference")
+))]
+pub use arkworks_msm::BlitzarMsm;
+#[cfg(all(
+    test,
+    feature = "arkworks",
+    any(feature = "blitzar-sys", feature = "reference")
+))]
+mod arkworks_msm_tests;
+
 mod generators;
-pub use generators::{get_curve25519_generators, get_one_curve25519_commit};
+pub use generators::{
+    derive_curve25519_generator, derive_curve25519_generators, derive_labeled_curve25519_generator,
+    derive_labeled_curve25519_generators, get_curve25519_generators, get_one_curve25519_commit,
+};
 
 #[cfg(test)]
 mod generators_tests;
+
+#[cfg(feature = "reference")]
+pub mod reference;
+#[cfg(all(feature = "blitzar-sys", not(feature = "reference")))]
+#[allow(dead_code)]
+mod reference;
+#[cfg(all(test, feature = "reference"))]
+mod reference_tests;
@@ -0,0 +1,292 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+    compute::{fixed_msm::remove_signed_offset, CurveId, MsmError, MsmHandle},
+    sequence::Sequence,
+};
+use rayon::prelude::*;
+use std::ops::{Add, SubAssign};
+
+#[derive(Copy, Clone)]
+enum ColumnData<'a> {
+    // little-endian scalars of `element_size` bytes each
+    Bytes { data: &'a [u8], element_size: usize },
+    // one bit per scalar
+    Bools(&'a [bool]),
+    // `len` scalars equal to one, used to correct signed columns
+    Ones,
+}
+
+#[derive(Copy, Clone)]
+struct Column<'a> {
+    data: ColumnData<'a>,
+    len: usize,
+    bit_width: u32,
+    is_signed: bool,
+}
+
+impl Column<'_> {
+    // Writes the bits of scalar `index` to `row`, starting at `bit_offset`.
+    fn write_scalar(&self, index: usize, row: &mut [u8], bit_offset: usize) {
+        let width = self.bit_width as usize;
+        match self.data {
+            ColumnData::Bytes { data, element_size } => {
+                let bytes = &data[index * element_size..(index + 1) * element_size];
+                if bit_offset % 8 == 0 {
+                    let start = bit_offset / 8;
+                    row[start..start + element_size].copy_from_slice(bytes);
+                } else {
+                    for bit in 0..width {
+                        if (bytes[bit / 8] >> (bit % 8)) & 1 == 1 {
+                            set_bit(row, bit_offset + bit);
+                        }
+                    }
+                }
+                if self.is_signed {
+                    // offset encoding: v + 2^(w-1) flips the sign bit of the two's complement
+                    flip_bit(row, bit_offset + width - 1);
+                }
+            }
+            ColumnData::Bools(data) => {
+                if data[index] {
+                    set_bit(row, bit_offset);
+                }
+            }
+            ColumnData::Ones => set_bit(row, bit_offset),
+        }
+    }
+}
+
+fn set_bit(row: &mut [u8], bit: usize) {
+    row[bit / 8] |= 1 << (bit % 8);
+}
+
+fn flip_bit(row: &mut [u8], bit: usize) {
+    row[bit / 8] ^= 1 << (bit % 8);
+}
+
+/// Builder for the bit-packed scalar layout of [MsmHandle::packed_msm] and
+/// [MsmHandle::vlen_msm].
+///
+/// Each pushed column becomes one MSM output. Columns can have different element
+/// widths and lengths: a column of `u16` values uses 16 bits per row, a column of
+/// [curve25519_dalek::scalar::Scalar] uses 256 bits, and [PackedScalars::push_bits]
+/// packs booleans in a single bit. Rows past the end of a column are zero.
+///
+/// Signed columns use offset encoding: a value v of a w-bit column is packed as the
+/// unsigned value v + 2^(w-1). For every distinct length of a signed column, the
+/// layout gets an extra 1-bit output whose scalars are all one, which yields the sum
+/// of the generators the column uses. [PackedScalars::packed_msm] and
+/// [PackedScalars::vlen_msm] subtract 2^(w-1) times that sum from the signed outputs.
+///
+/// # Example
+///```no_run
+#[doc = include_str!("../../examples/packed_scalars_msm.rs")]
+///```
+#[derive(Clone, Default)]
+pub struct PackedScalars<'a> {
+    columns: Vec<Column<'a>>,
+}
+
+impl<'a> PackedScalars<'a> {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a column using all the bits of its elements.
+    pub fn push(&mut self, column: Sequence<'a>) -> &mut Self {
+        self.columns.push(Column {
+            data: ColumnData::Bytes {
+                data: column.as_bytes(),
+                element_size: column.element_size(),
+            },
+            len: column.len(),
+            bit_width: 8 * column.element_size() as u32,
+            is_signed: column.is_signed(),
+        });
+        self
+    }
+
+    /// Appends a column of booleans using a single bit per element.
+    pub fn push_bits(&mut self, column: &'a [bool]) -> &mut Self {
+        self.columns.push(Column {
+            data: ColumnData::Bools(column),
+            len: column.len(),
+            bit_width: 1,
+            is_signed: false,
+        });
+        self
+    }
+
+    /// Number of pushed columns, which is the number of MSM outputs.
+    pub fn num_outputs(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Number of scalars per output of the packed layout, the length of the longest column.
+    pub fn num_rows(&self) -> usize {
+        self.columns.iter().map(|c| c.len).max().unwrap_or(0)
+    }
+
+    /// Number of bits used for each output, including the correction outputs of
+    /// signed columns that follow the pushed columns.
+    pub fn output_bit_table(&self) -> Vec<u32> {
+        self.layout().iter().map(|c| c.bit_width).collect()
+    }
+
+    /// Length of each output, including the correction outputs of signed columns.
+    pub fn output_lengths(&self) -> Vec<u32> {
+        self.layout().iter().map(|c| c.len as u32).collect()
+    }
+
+    /// Packed column-major scalars for [MsmHandle::packed_msm], matching
+    /// [PackedScalars::output_bit_table].
+    pub fn packed_scalars(&self) -> Vec<u8> {
+        pack(&self.layout(), self.num_rows())
+    }
+
+    /// Computes the MSM of every pushed column with [MsmHandle::packed_msm].
+    ///
+    /// `res` must have one entry per pushed column. Signed columns are corrected,
+    /// so `res` holds the MSMs of the signed values.
+    pub fn packed_msm<T>(&self, handle: &MsmHandle<T>, res: &mut [T]) -> Result<(), MsmError>
+    where
+        T: CurveId + Clone + Default + for<'b> SubAssign<&'b T>,
+        for<'b> &'b T: Add<&'b T, Output = T>,
+    {
+        self.check_outputs(res)?;
+        let layout = self.layout();
+        let output_bit_table: Vec<u32> = layout.iter().map(|c| c.bit_width).collect();
+        let scalars = pack(&layout, self.num_rows());
+        let mut full_res = vec![T::default(); layout.len()];
+        handle.packed_msm(&mut full_res, &output_bit_table, &scalars)?;
+        self.correct(&layout, full_res, res);
+        Ok(())
+    }
+
+    /// Computes the MSM of every pushed column with [MsmHandle::vlen_msm], so
+    /// each column only uses as many generators as it has elements.
+    ///
+    /// The columns do not need to be sorted by length: they are reordered internally
+    /// and the results are written in the order the columns were pushed.
+    pub fn vlen_msm<T>(&self, handle: &MsmHandle<T>, res: &mut [T]) -> Result<(), MsmError>
+    where
+        T: CurveId + Clone + Default + for<'b> SubAssign<&'b T>,
+        for<'b> &'b T: Add<&'b T, Output = T>,
+    {
+        self.check_outputs(res)?;
+        let layout = self.layout();
+        let mut order: Vec<usize> = (0..layout.len()).collect();
+        order.sort_by_key(|&i| layout[i].len);
+        let sorted: Vec<Column> = order.iter().map(|&i| layout[i]).collect();
+        let output_bit_table: Vec<u32> = sorted.iter().map(|c| c.bit_width).collect();
+        let output_lengths: Vec<u32> = sorted.iter().map(|c| c.len as u32).collect();
+        let scalars = pack(&sorted, self.num_rows());
+        let mut sorted_res = vec![T::default(); layout.len()];
+        handle.vlen_msm(
+            &mut sorted_res,
+            &output_bit_table,
+            &output_lengths,
+            &scalars,
+        )?;
+        let mut full_res = vec![T::default(); layout.len()];
+        for (res_i, &i) in sorted_res.into_iter().zip(&order) {
+            full_res[i] = res_i;
+        }
+        self.correct(&layout, full_res, res);
+        Ok(())
+    }
+
+    fn check_outputs<T>(&self, res: &[T]) -> Result<(), MsmError> {
+        if res.len() != self.num_outputs() {
+            return Err(MsmError::OutputTableLengthMismatch {
+                expected: self.num_outputs(),
+                found: res.len(),
+            });
+        }
+        Ok(())
+    }
+
+    // The pushed columns, followed by an all-ones column for every distinct length
+    // of a signed column.
+    fn layout(&self) -> Vec<Column<'a>> {
+        let mut layout = self.columns.clone();
+        for len in self.correction_lengths() {
+            layout.push(Column {
+                data: ColumnData::Ones,
+                len,
+                bit_width: 1,
+                is_signed: false,
+            });
+        }
+        layout
+    }
+
+    // Distinct lengths of signed columns, in the order they first appear.
+    fn correction_lengths(&self) -> Vec<usize> {
+        let mut lengths = Vec::new();
+        for column in self.columns.iter().filter(|c| c.is_signed) {
+            if !lengths.contains(&column.len) {
+                lengths.push(column.len);
+            }
+        }
+        lengths
+    }
+
+    // Writes the pushed outputs to `res`, removing the offset of signed columns.
+    fn correct<T>(&self, layout: &[Column], full_res: Vec<T>, res: &mut [T])
+    where
+        T: Clone + for<'b> SubAssign<&'b T>,
+        for<'b> &'b T: Add<&'b T, Output = T>,
+    {
+        let num_outputs = self.num_outputs();
+        let correction_lengths = self.correction_lengths();
+        for (j, column) in self.columns.iter().enumerate() {
+            res[j] = full_res[j].clone();
+            if !column.is_signed {
+                continue;
+            }
+            let k = correction_lengths
+                .iter()
+                .position(|len| *len == column.len)
+                .expect("every signed column has a correction output");
+            debug_assert_eq!(layout[num_outputs + k].len, column.len);
+            remove_signed_offset(&mut res[j], &full_res[num_outputs + k], column.bit_width);
+        }
+    }
+}
+
+// Packs the columns in the given order into `num_rows` rows.
+fn pack(columns: &[Column], num_rows: usize) -> Vec<u8> {
+    let bit_sum: usize = columns.iter().map(|c| c.bit_width as usize).sum();
+    let row_num_bytes = bit_sum.div_ceil(8);
+    let mut scalars = vec![0_u8; row_num_bytes * num_rows];
+    if row_num_bytes == 0 {
+        return scalars;
+    }
+    scalars
+        .par_chunks_mut(row_num_bytes)
+        .enumerate()
+        .for_each(|(index, row)| {
+            let mut bit_offset = 0;
+            for column in columns {
+                if index < column.len {
+                    column.write_scalar(index, row, bit_offset);
+                }
+                bit_offset += column.bit_width as usize;
+            }
+        });
+    scalars
+}
@@ -0,0 +1,150 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use ark_std::UniformRand;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use rand_core::OsRng;
+
+fn random_generators(n: usize) -> Vec<RistrettoPoint> {
+    let mut rng = OsRng;
+    (0..n).map(|_| RistrettoPoint::random(&mut rng)).collect()
+}
+
+fn expected_msm(generators: &[RistrettoPoint], scalars: &[Scalar]) -> RistrettoPoint {
+    scalars.iter().zip(generators).map(|(s, g)| s * g).sum()
+}
+
+#[test]
+fn we_can_build_the_packed_layout_of_mixed_width_columns() {
+    let flags = [true, false, true];
+    let small: Vec<u8> = vec![1, 2];
+    let wide: Vec<u16> = vec![0x0304, 0x0506, 0x0708];
+
+    let mut scalars = PackedScalars::new();
+    scalars
+        .push_bits(&flags)
+        .push((&small).into())
+        .push((&wide).into());
+    assert_eq!(scalars.num_outputs(), 3);
+    assert_eq!(scalars.num_rows(), 3);
+    assert_eq!(scalars.output_bit_table(), [1, 8, 16]);
+    assert_eq!(scalars.output_lengths(), [3, 2, 3]);
+
+    // each row has 25 bits, packed LSB first into 4 bytes
+    assert_eq!(
+        scalars.packed_scalars(),
+        [
+            0x03, 0x08, 0x06, 0x00, // 1 | 1 << 1 | 0x0304 << 9
+            0x04, 0x0c, 0x0a, 0x00, // 0 | 2 << 1 | 0x0506 << 9
+            0x01, 0x10, 0x0e, 0x00, // 1 | 0 << 1 | 0x0708 << 9
+        ]
+    );
+}
+
+#[test]
+fn signed_columns_add_correction_outputs() {
+    let a: Vec<i8> = vec![-1, 2];
+    let b: Vec<i16> = vec![3, -4];
+    let c: Vec<i8> = vec![5];
+
+    let mut scalars = PackedScalars::new();
+    scalars
+        .push((&a).into())
+        .push((&b).into())
+        .push((&c).into());
+
+    // one all-ones output per distinct signed length
+    assert_eq!(scalars.output_bit_table(), [8, 16, 8, 1, 1]);
+    assert_eq!(scalars.output_lengths(), [2, 2, 1, 2, 1]);
+}
+
+#[test]
+fn we_can_compute_packed_msms_of_mixed_columns() {
+    let generators = random_generators(4);
+    let handle = MsmHandle::new(&generators);
+
+    let flags = [true, false, true, true];
+    let counts: Vec<u32> = vec![7, 300, 9];
+    let deltas: Vec<i16> = vec![-2, 5, i16::MIN, i16::MAX];
+    let negatives: Vec<i64> = vec![-1];
+    let mut rng = OsRng;
+    let wide: Vec<Scalar> = (0..2).map(|_| Scalar::random(&mut rng)).collect();
+
+    let mut scalars = PackedScalars::new();
+    scalars
+        .push_bits(&flags)
+        .push((&counts).into())
+        .push((&deltas).into())
+        .push((&negatives).into())
+        .push((&wide).into());
+
+    let to_scalar = |v: i64| {
+        if v < 0 {
+            -Scalar::from(v.unsigned_abs())
+        } else {
+            Scalar::from(v as u64)
+        }
+    };
+    let expected = [
+        expected_msm(&generators, &flags.map(|f| Scalar::from(f as u64))),
+        expected_msm(
+            &generators,
+            &counts.iter().map(|v| Scalar::from(*v)).collect::<Vec<_>>(),
+        ),
+        expected_msm(
+            &generators,
+            &deltas
+                .iter()
+                .map(|v| to_scalar(*v as i64))
+                .collect::<Vec<_>>(),
+        ),
+        expected_msm(&generators, &[to_scalar(-1)]),
+        expected_msm(&generators, &wide),
+    ];
+
+    let mut res = vec![RistrettoPoint::default(); 5];
+    scalars.packed_msm(&handle, &mut res).unwrap();
+    assert_eq!(res, expected);
+
+    let mut res = vec![RistrettoPoint::default(); 5];
+    scalars.vlen_msm(&handle, &mut res).unwrap();
+    assert_eq!(res, expected);
+
+    // there must be one result per column
+    let mut res = vec![RistrettoPoint::default(); 4];
+    assert!(matches!(
+        scalars.packed_msm(&handle, &mut res),
+        Err(MsmError::OutputTableLengthMismatch { .. })
+    ));
+}
+
+#[test]
+fn we_can_compute_packed_msms_of_signed_columns_on_short_weierstrass_curves() {
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<ark_bn254::G1Affine> = (0..3)
+        .map(|_| ark_bn254::G1Affine::rand(&mut rng))
+        .collect();
+    let handle = MsmHandle::<ElementP2<ark_bn254::g1::Config>>::new_with_affine(&generators);
+
+    let values: Vec<i32> = vec![-3, 4, -5];
+    let mut scalars = PackedScalars::new();
+    scalars.push((&values).into());
+    let mut res = vec![ElementP2::<ark_bn254::g1::Config>::default(); 1];
+    scalars.packed_msm(&handle, &mut res).unwrap();
+
+    let expected = generators[1] * ark_bn254::Fr::from(4_u64)
+        - generators[0] * ark_bn254::Fr::from(3_u64)
+        - generators[2] * ark_bn254::Fr::from(5_u64);
+    assert_eq!(res[0], ElementP2::from(expected));
+}
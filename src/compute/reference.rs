@@ -0,0 +1,413 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pure-Rust reference implementation of the backend.
+//!
+//! The functions of this module compute the same results as their counterparts in
+//! [crate::compute], using `curve25519-dalek` and arkworks instead of blitzar-sys.
+//! They are much slower, but can be used to cross-check the backend.
+//!
+//! When the reference backend is selected with [crate::compute::init_reference_backend],
+//! or with the `BLITZAR_BACKEND=reference` environment variable, the commitments,
+//! generators, [crate::compute::MsmHandle] and [crate::proof::InnerProductProof] of
+//! this crate use this implementation instead of blitzar-sys.
+
+use crate::{
+    compute::{
+        fixed_msm::count_row_bytes,
+        generators::{derive_curve25519_generators, derive_one_curve25519_commit},
+        CompressedBls12381G1Commitment, CurveId, ElementP2, GrumpkinAffine, GrumpkinConfig,
+    },
+    sequence::Sequence,
+};
+use ark_ec::{
+    short_weierstrass::{Affine, Projective, SWCurveConfig},
+    CurveGroup, VariableBaseMSM,
+};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::VartimeMultiscalarMul,
+};
+use rayon::prelude::*;
+use std::ops::Neg;
+
+// Curve operations used by the reference implementation.
+trait ReferenceCurve: Sized + Sync {
+    type Scalar: Neg<Output = Self::Scalar> + Send + Sync;
+    type Output: Send;
+
+    // Reduces a little-endian integer of at most 32 bytes modulo the group order.
+    fn scalar_from_le_bytes(bytes: &[u8]) -> Self::Scalar;
+
+    // Computes the MSM of `scalars` with the first `scalars.len()` bases.
+    fn msm(bases: &[Self], scalars: &[Self::Scalar]) -> Self::Output;
+}
+
+impl ReferenceCurve for RistrettoPoint {
+    type Scalar = Scalar;
+    type Output = RistrettoPoint;
+
+    fn scalar_from_le_bytes(bytes: &[u8]) -> Scalar {
+        let mut padded = [0_u8; 32];
+        padded[..bytes.len()].copy_from_slice(bytes);
+        Scalar::from_bytes_mod_order(padded)
+    }
+
+    fn msm(bases: &[Self], scalars: &[Scalar]) -> RistrettoPoint {
+        RistrettoPoint::vartime_multiscalar_mul(scalars, &bases[..scalars.len()])
+    }
+}
+
+impl<C: SWCurveConfig> ReferenceCurve for Affine<C> {
+    type Scalar = C::ScalarField;
+    type Output = Projective<C>;
+
+    fn scalar_from_le_bytes(bytes: &[u8]) -> C::ScalarField {
+        C::ScalarField::from_le_bytes_mod_order(bytes)
+    }
+
+    fn msm(bases: &[Self], scalars: &[C::ScalarField]) -> Projective<C> {
+        Projective::<C>::msm_unchecked(&bases[..scalars.len()], scalars)
+    }
+}
+
+// Converts the elements of a sequence to scalars, reading signed elements as
+// two's complement integers.
+fn sequence_scalars<C: ReferenceCurve>(data: &Sequence) -> Vec<C::Scalar> {
+    let element_size = data.element_size();
+    data.as_bytes()
+        .chunks_exact(element_size)
+        .map(|bytes| {
+            if data.is_signed() && bytes[element_size - 1] & 0x80 != 0 {
+                // the magnitude of a negative value fits in its unsigned counterpart
+                let mut magnitude = bytes.to_vec();
+                let mut carry = true;
+                for byte in magnitude.iter_mut() {
+                    (*byte, carry) = (!*byte).overflowing_add(carry as u8);
+                }
+                -C::scalar_from_le_bytes(&magnitude)
+            } else {
+                C::scalar_from_le_bytes(bytes)
+            }
+        })
+        .collect()
+}
+
+fn commit<C: ReferenceCurve>(generators: &[C], data: &Sequence) -> C::Output {
+    assert!(
+        data.len() <= generators.len(),
+        "generators has a length smaller than the longest sequence in the input data"
+    );
+    C::msm(generators, &sequence_scalars::<C>(data))
+}
+
+fn compute_commitments<C: ReferenceCurve, T: Send>(
+    commitments: &mut [T],
+    data: &[Sequence],
+    generators: &[C],
+    convert: impl Fn(C::Output) -> T + Sync,
+) {
+    commitments
+        .par_iter_mut()
+        .zip(data)
+        .for_each(|(commitment, data)| *commitment = convert(commit(generators, data)));
+}
+
+/// Reference implementation of [crate::compute::compute_curve25519_commitments].
+pub fn compute_curve25519_commitments(
+    commitments: &mut [CompressedRistretto],
+    data: &[Sequence],
+    offset_generators: u64,
+) {
+    let n = data.iter().map(Sequence::len).max().unwrap_or(0);
+    let mut generators = vec![RistrettoPoint::default(); n];
+    get_curve25519_generators(&mut generators, offset_generators);
+    compute_curve25519_commitments_with_generators(commitments, data, &generators);
+}
+
+/// Reference implementation of [crate::compute::compute_curve25519_commitments_with_generators].
+pub fn compute_curve25519_commitments_with_generators(
+    commitments: &mut [CompressedRistretto],
+    data: &[Sequence],
+    generators: &[RistrettoPoint],
+) {
+    compute_commitments(commitments, data, generators, |c| c.compress());
+}
+
+/// Reference implementation of [crate::compute::compute_bls12_381_g1_commitments_with_generators].
+pub fn compute_bls12_381_g1_commitments_with_generators(
+    commitments: &mut [[u8; 48]],
+    data: &[Sequence],
+    generators: &[ark_bls12_381::G1Affine],
+) {
+    compute_commitments(commitments, data, generators, |c| {
+        *CompressedBls12381G1Commitment::from(c.into_affine()).as_bytes()
+    });
+}
+
+/// Reference implementation of [crate::compute::compute_bn254_g1_uncompressed_commitments_with_generators].
+pub fn compute_bn254_g1_uncompressed_commitments_with_generators(
+    commitments: &mut [ark_bn254::G1Affine],
+    data: &[Sequence],
+    generators: &[ark_bn254::G1Affine],
+) {
+    compute_commitments(commitments, data, generators, |c| c.into_affine());
+}
+
+/// Reference implementation of [crate::compute::compute_grumpkin_uncompressed_commitments_with_generators].
+pub fn compute_grumpkin_uncompressed_commitments_with_generators(
+    commitments: &mut [GrumpkinAffine],
+    data: &[Sequence],
+    generators: &[GrumpkinAffine],
+) {
+    compute_commitments(commitments, data, generators, |c| c.into_affine());
+}
+
+/// Reference implementation of [crate::compute::get_curve25519_generators].
+pub fn get_curve25519_generators(generators: &mut [RistrettoPoint], offset_generators: u64) {
+    derive_curve25519_generators(generators, offset_generators);
+}
+
+/// Reference implementation of [crate::compute::get_one_curve25519_commit].
+pub fn get_one_curve25519_commit(n: u64) -> RistrettoPoint {
+    derive_one_curve25519_commit(n)
+}
+
+// Reinterprets a slice of `T` as a slice of `U`.
+//
+// Safety: `T` and `U` must be the same type. This holds for the generic element
+// type of an `MsmHandle` and the concrete type of its curve id.
+unsafe fn cast_slice<T, U>(values: &[T]) -> &[U] {
+    debug_assert_eq!(std::mem::size_of::<T>(), std::mem::size_of::<U>());
+    std::slice::from_raw_parts(values.as_ptr() as *const U, values.len())
+}
+
+// Mutable version of `cast_slice`, with the same safety requirements.
+unsafe fn cast_slice_mut<T, U>(values: &mut [T]) -> &mut [U] {
+    debug_assert_eq!(std::mem::size_of::<T>(), std::mem::size_of::<U>());
+    std::slice::from_raw_parts_mut(values.as_mut_ptr() as *mut U, values.len())
+}
+
+const RISTRETTO: u32 = <RistrettoPoint as CurveId>::CURVE_ID;
+const BLS12_381: u32 = <ElementP2<ark_bls12_381::g1::Config> as CurveId>::CURVE_ID;
+const BN254: u32 = <ElementP2<ark_bn254::g1::Config> as CurveId>::CURVE_ID;
+const GRUMPKIN: u32 = <ElementP2<GrumpkinConfig> as CurveId>::CURVE_ID;
+const BLS12_381_G2: u32 = <ElementP2<ark_bls12_381::g2::Config> as CurveId>::CURVE_ID;
+const BN254_G2: u32 = <ElementP2<ark_bn254::g2::Config> as CurveId>::CURVE_ID;
+
+/// Generators of an [crate::compute::MsmHandle] that uses the reference backend.
+pub(crate) enum MsmGenerators {
+    Ristretto(Vec<RistrettoPoint>),
+    Bls12381(Vec<ark_bls12_381::G1Affine>),
+    Bn254(Vec<ark_bn254::G1Affine>),
+    Grumpkin(Vec<GrumpkinAffine>),
+    Bls12381G2(Vec<ark_bls12_381::G2Affine>),
+    Bn254G2(Vec<ark_bn254::G2Affine>),
+}
+
+impl MsmGenerators {
+    pub(crate) fn new<T: CurveId>(generators: &[T]) -> Self {
+        // the curve id determines the concrete type of T
+        unsafe {
+            match T::CURVE_ID {
+                RISTRETTO => Self::Ristretto(cast_slice(generators).to_vec()),
+                BLS12_381 => Self::Bls12381(ElementP2::batch_normalize(cast_slice(generators))),
+                BN254 => Self::Bn254(ElementP2::batch_normalize(cast_slice(generators))),
+                GRUMPKIN => Self::Grumpkin(ElementP2::batch_normalize(cast_slice(generators))),
+                BLS12_381_G2 => {
+                    Self::Bls12381G2(ElementP2::batch_normalize(cast_slice(generators)))
+                }
+                BN254_G2 => Self::Bn254G2(ElementP2::batch_normalize(cast_slice(generators))),
+                curve_id => panic!("unsupported curve id {curve_id}"),
+            }
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Self::Ristretto(g) => g.len(),
+            Self::Bls12381(g) => g.len(),
+            Self::Bn254(g) => g.len(),
+            Self::Grumpkin(g) => g.len(),
+            Self::Bls12381G2(g) => g.len(),
+            Self::Bn254G2(g) => g.len(),
+        }
+    }
+
+    /// Computes a variable length MSM, see [crate::compute::MsmHandle::vlen_msm].
+    ///
+    /// The arguments must have been validated by the handle; the lengths do not
+    /// need to be sorted.
+    pub(crate) fn vlen_msm<T: CurveId>(
+        &self,
+        res: &mut [T],
+        output_bit_table: &[u32],
+        output_lengths: &[u32],
+        scalars: &[u8],
+    ) {
+        self.vlen_msm_range(res, 0, output_bit_table, output_lengths, scalars);
+    }
+
+    /// Computes a variable length MSM with the generators from `start` on, see
+    /// [crate::compute::MsmHandle::vlen_msm_range].
+    ///
+    /// The arguments must have been validated by the handle.
+    pub(crate) fn vlen_msm_range<T: CurveId>(
+        &self,
+        res: &mut [T],
+        start: usize,
+        output_bit_table: &[u32],
+        output_lengths: &[u32],
+        scalars: &[u8],
+    ) {
+        // the curve id determines the concrete type of T
+        unsafe {
+            match self {
+                Self::Ristretto(g) => {
+                    let res: &mut [RistrettoPoint] = cast_slice_mut(res);
+                    let outputs =
+                        packed_msm(&g[start..], output_bit_table, output_lengths, scalars);
+                    res.copy_from_slice(&outputs);
+                }
+                Self::Bls12381(g) => {
+                    let res = cast_slice_mut(res);
+                    write_projective(
+                        res,
+                        packed_msm(&g[start..], output_bit_table, output_lengths, scalars),
+                    );
+                }
+                Self::Bn254(g) => {
+                    let res = cast_slice_mut(res);
+                    write_projective(
+                        res,
+                        packed_msm(&g[start..], output_bit_table, output_lengths, scalars),
+                    );
+                }
+                Self::Grumpkin(g) => {
+                    let res = cast_slice_mut(res);
+                    write_projective(
+                        res,
+                        packed_msm(&g[start..], output_bit_table, output_lengths, scalars),
+                    );
+                }
+                Self::Bls12381G2(g) => {
+                    let res = cast_slice_mut(res);
+                    write_projective(
+                        res,
+                        packed_msm(&g[start..], output_bit_table, output_lengths, scalars),
+                    );
+                }
+                Self::Bn254G2(g) => {
+                    let res = cast_slice_mut(res);
+                    write_projective(
+                        res,
+                        packed_msm(&g[start..], output_bit_table, output_lengths, scalars),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Serializes the generators, for [crate::compute::MsmHandle::write_to_file].
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let res = match self {
+            Self::Ristretto(g) => {
+                g.iter()
+                    .for_each(|p| bytes.extend_from_slice(p.compress().as_bytes()));
+                Ok(())
+            }
+            Self::Bls12381(g) => g.serialize_uncompressed(&mut bytes),
+            Self::Bn254(g) => g.serialize_uncompressed(&mut bytes),
+            Self::Grumpkin(g) => g.serialize_uncompressed(&mut bytes),
+            Self::Bls12381G2(g) => g.serialize_uncompressed(&mut bytes),
+            Self::Bn254G2(g) => g.serialize_uncompressed(&mut bytes),
+        };
+        res.expect("serialization to a vector cannot fail");
+        bytes
+    }
+
+    /// Deserializes generators written by [MsmGenerators::to_bytes].
+    pub(crate) fn from_bytes(curve_id: u32, bytes: &[u8]) -> Option<Self> {
+        match curve_id {
+            RISTRETTO => {
+                if bytes.len() % 32 != 0 {
+                    return None;
+                }
+                bytes
+                    .chunks_exact(32)
+                    .map(|chunk| CompressedRistretto::from_slice(chunk).ok()?.decompress())
+                    .collect::<Option<_>>()
+                    .map(Self::Ristretto)
+            }
+            BLS12_381 => Vec::deserialize_uncompressed(bytes)
+                .ok()
+                .map(Self::Bls12381),
+            BN254 => Vec::deserialize_uncompressed(bytes).ok().map(Self::Bn254),
+            GRUMPKIN => Vec::deserialize_uncompressed(bytes)
+                .ok()
+                .map(Self::Grumpkin),
+            BLS12_381_G2 => Vec::deserialize_uncompressed(bytes)
+                .ok()
+                .map(Self::Bls12381G2),
+            BN254_G2 => Vec::deserialize_uncompressed(bytes).ok().map(Self::Bn254G2),
+            _ => None,
+        }
+    }
+}
+
+fn write_projective<C: SWCurveConfig>(res: &mut [ElementP2<C>], outputs: Vec<Projective<C>>) {
+    for (res_j, output) in res.iter_mut().zip(outputs) {
+        *res_j = output.into();
+    }
+}
+
+// Computes the MSM of every output of the packed layout of `MsmHandle::vlen_msm`.
+fn packed_msm<C: ReferenceCurve>(
+    generators: &[C],
+    output_bit_table: &[u32],
+    output_lengths: &[u32],
+    scalars: &[u8],
+) -> Vec<C::Output> {
+    let row_num_bytes = count_row_bytes(output_bit_table);
+    let bit_offsets: Vec<usize> = output_bit_table
+        .iter()
+        .scan(0, |offset, bits| {
+            let start = *offset;
+            *offset += *bits as usize;
+            Some(start)
+        })
+        .collect();
+    (0..output_bit_table.len())
+        .into_par_iter()
+        .map(|j| {
+            let bits = output_bit_table[j] as usize;
+            let column: Vec<C::Scalar> = (0..output_lengths[j] as usize)
+                .map(|i| {
+                    let row = &scalars[i * row_num_bytes..(i + 1) * row_num_bytes];
+                    let mut bytes = [0_u8; 32];
+                    for bit in 0..bits {
+                        let src = bit_offsets[j] + bit;
+                        bytes[bit / 8] |= ((row[src / 8] >> (src % 8)) & 1) << (bit % 8);
+                    }
+                    C::scalar_from_le_bytes(&bytes[..bits.div_ceil(8)])
+                })
+                .collect();
+            C::msm(generators, &column)
+        })
+        .collect()
+}
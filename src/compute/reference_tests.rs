@@ -0,0 +1,175 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{reference::MsmGenerators, *};
+use crate::sequence::Sequence;
+use ark_std::UniformRand;
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+};
+use rand_core::OsRng;
+
+fn mixed_sequences<'a>(
+    unsigned: &'a [u8],
+    signed: &'a [i32],
+    wide: &'a [i128],
+    scalars: &'a [Scalar],
+) -> Vec<Sequence<'a>> {
+    vec![unsigned.into(), signed.into(), wide.into(), scalars.into()]
+}
+
+#[test]
+fn reference_curve25519_commitments_match_the_backend() {
+    let mut rng = OsRng;
+    let unsigned: Vec<u8> = vec![1, 255, 0, 7];
+    let signed: Vec<i32> = vec![-1, i32::MIN, i32::MAX];
+    let wide: Vec<i128> = vec![i128::MIN, -3];
+    let scalars: Vec<Scalar> = (0..5).map(|_| Scalar::random(&mut rng)).collect();
+    let data = mixed_sequences(&unsigned, &signed, &wide, &scalars);
+
+    let mut expected = vec![CompressedRistretto::default(); data.len()];
+    compute_curve25519_commitments(&mut expected, &data, 5);
+    let mut commitments = vec![CompressedRistretto::default(); data.len()];
+    reference::compute_curve25519_commitments(&mut commitments, &data, 5);
+    assert_eq!(commitments, expected);
+
+    let generators: Vec<RistrettoPoint> =
+        (0..5).map(|_| RistrettoPoint::random(&mut rng)).collect();
+    compute_curve25519_commitments_with_generators(&mut expected, &data, &generators);
+    reference::compute_curve25519_commitments_with_generators(&mut commitments, &data, &generators);
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+fn reference_short_weierstrass_commitments_match_the_backend() {
+    let mut rng = ark_std::test_rng();
+    let unsigned: Vec<u8> = vec![1, 255, 0, 7];
+    let signed: Vec<i32> = vec![-1, i32::MIN, i32::MAX];
+    let wide: Vec<i128> = vec![i128::MIN, -3];
+    let scalars: Vec<Scalar> = (0..5).map(|_| Scalar::random(&mut OsRng)).collect();
+    let data = mixed_sequences(&unsigned, &signed, &wide, &scalars);
+
+    let generators: Vec<ark_bls12_381::G1Affine> = (0..5)
+        .map(|_| ark_bls12_381::G1Affine::rand(&mut rng))
+        .collect();
+    let mut expected = vec![[0_u8; 48]; data.len()];
+    compute_bls12_381_g1_commitments_with_generators(&mut expected, &data, &generators);
+    let mut commitments = vec![[0_u8; 48]; data.len()];
+    reference::compute_bls12_381_g1_commitments_with_generators(
+        &mut commitments,
+        &data,
+        &generators,
+    );
+    assert_eq!(commitments, expected);
+
+    let generators: Vec<ark_bn254::G1Affine> = (0..5)
+        .map(|_| ark_bn254::G1Affine::rand(&mut rng))
+        .collect();
+    let mut expected = vec![ark_bn254::G1Affine::default(); data.len()];
+    compute_bn254_g1_uncompressed_commitments_with_generators(&mut expected, &data, &generators);
+    let mut commitments = vec![ark_bn254::G1Affine::default(); data.len()];
+    reference::compute_bn254_g1_uncompressed_commitments_with_generators(
+        &mut commitments,
+        &data,
+        &generators,
+    );
+    assert_eq!(commitments, expected);
+
+    let generators: Vec<GrumpkinAffine> = (0..5).map(|_| GrumpkinAffine::rand(&mut rng)).collect();
+    let mut expected = vec![GrumpkinAffine::default(); data.len()];
+    compute_grumpkin_uncompressed_commitments_with_generators(&mut expected, &data, &generators);
+    let mut commitments = vec![GrumpkinAffine::default(); data.len()];
+    reference::compute_grumpkin_uncompressed_commitments_with_generators(
+        &mut commitments,
+        &data,
+        &generators,
+    );
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+fn reference_generators_match_the_backend() {
+    let mut expected = vec![RistrettoPoint::default(); 10];
+    get_curve25519_generators(&mut expected, 17);
+    let mut generators = vec![RistrettoPoint::default(); 10];
+    reference::get_curve25519_generators(&mut generators, 17);
+    assert_eq!(generators, expected);
+
+    for n in [0, 1, 10] {
+        assert_eq!(
+            reference::get_one_curve25519_commit(n),
+            get_one_curve25519_commit(n)
+        );
+    }
+}
+
+#[test]
+fn reference_msms_match_the_backend() {
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<ElementP2<ark_bn254::g1::Config>> = (0..4)
+        .map(|_| ark_bn254::G1Affine::rand(&mut rng).into())
+        .collect();
+    let handle = MsmHandle::new(&generators);
+    let reference = MsmGenerators::new(&generators);
+
+    let output_bit_table = [3, 16, 1, 256];
+    let output_lengths = [1, 2, 4, 4];
+    let scalars: Vec<u8> = (0..4 * 35).map(|i| (i * 37 + 11) as u8).collect();
+    let mut expected = vec![ElementP2::default(); 4];
+    handle
+        .vlen_msm(&mut expected, &output_bit_table, &output_lengths, &scalars)
+        .unwrap();
+    let mut res = vec![ElementP2::default(); 4];
+    reference.vlen_msm(&mut res, &output_bit_table, &output_lengths, &scalars);
+    assert_eq!(res, expected);
+
+    let generators: Vec<RistrettoPoint> =
+        (0..4).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let handle = MsmHandle::new(&generators);
+    let reference = MsmGenerators::new(&generators);
+    let mut expected = vec![RistrettoPoint::default(); 4];
+    handle
+        .vlen_msm(&mut expected, &output_bit_table, &output_lengths, &scalars)
+        .unwrap();
+    let mut res = vec![RistrettoPoint::default(); 4];
+    reference.vlen_msm(&mut res, &output_bit_table, &output_lengths, &scalars);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn reference_msm_generators_can_be_serialized() {
+    let generators: Vec<RistrettoPoint> =
+        (0..3).map(|_| RistrettoPoint::random(&mut OsRng)).collect();
+    let bytes = MsmGenerators::new(&generators).to_bytes();
+    let curve_id = <RistrettoPoint as curve::CurveId>::CURVE_ID;
+    let MsmGenerators::Ristretto(decoded) = MsmGenerators::from_bytes(curve_id, &bytes).unwrap()
+    else {
+        panic!("wrong curve");
+    };
+    assert_eq!(decoded, generators);
+    assert!(MsmGenerators::from_bytes(curve_id, &bytes[1..]).is_none());
+
+    let mut rng = ark_std::test_rng();
+    let generators: Vec<ElementP2<GrumpkinConfig>> = (0..3)
+        .map(|_| GrumpkinAffine::rand(&mut rng).into())
+        .collect();
+    let bytes = MsmGenerators::new(&generators).to_bytes();
+    let curve_id = <ElementP2<GrumpkinConfig> as curve::CurveId>::CURVE_ID;
+    let MsmGenerators::Grumpkin(decoded) = MsmGenerators::from_bytes(curve_id, &bytes).unwrap()
+    else {
+        panic!("wrong curve");
+    };
+    assert_eq!(decoded, ElementP2::batch_normalize(&generators));
+}
@@ -0,0 +1,384 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::{
+    compute_bls12_381_g1_commitments_with_generators,
+    compute_bn254_g1_uncompressed_commitments_with_generators,
+    compute_curve25519_commitments_with_generators,
+    compute_grumpkin_uncompressed_commitments_with_generators, get_curve25519_generators,
+    CommitmentError, CompressedBls12381G1Commitment, GrumpkinAffine,
+};
+use crate::sequence::Sequence;
+use ark_ec::{
+    short_weierstrass::{Affine, Projective, SWCurveConfig},
+    AffineRepr, CurveGroup,
+};
+use ark_serialize::CanonicalDeserialize;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use rayon::prelude::*;
+
+// Rows whose generators are at most this far apart are fetched in one call.
+const MAX_GENERATOR_GAP: u64 = 32;
+
+// Checks that the rows are distinct and that there is one column of old values,
+// and of new values if given, per commitment, each with one value per row.
+fn check_rows(
+    num_commitments: usize,
+    rows: &[u64],
+    old_values: &[Sequence],
+    new_values: Option<&[Sequence]>,
+) -> Result<(), CommitmentError> {
+    let mut sorted_rows = rows.to_vec();
+    sorted_rows.sort_unstable();
+    if let Some(pair) = sorted_rows.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(CommitmentError::DuplicateRow { row: pair[0] });
+    }
+
+    let check = |expected: usize, found: usize| {
+        if expected != found {
+            return Err(CommitmentError::LengthMismatch { expected, found });
+        }
+        Ok(())
+    };
+    for values in std::iter::once(old_values).chain(new_values) {
+        check(num_commitments, values.len())?;
+        for column in values {
+            check(rows.len(), column.len())?;
+        }
+    }
+    Ok(())
+}
+
+// Adds `commit(new_values) - commit(old_values)` to each commitment, where the
+// column values of row `k` use the generator `generators[rows[k]]`.
+fn adjust_sw_commitments<C: SWCurveConfig>(
+    commitments: &mut [Affine<C>],
+    rows: &[u64],
+    old_values: &[Sequence],
+    new_values: Option<&[Sequence]>,
+    generators: &[Affine<C>],
+    commit: impl Fn(&mut [Affine<C>], &[Sequence], &[Affine<C>]),
+) -> Result<(), CommitmentError> {
+    check_rows(commitments.len(), rows, old_values, new_values)?;
+    let row_generators = rows
+        .iter()
+        .map(|&row| {
+            generators
+                .get(row as usize)
+                .copied()
+                .ok_or(CommitmentError::RowOutOfRange {
+                    row,
+                    num_generators: generators.len() as u64,
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut old_commitments = vec![Affine::<C>::identity(); commitments.len()];
+    commit(&mut old_commitments, old_values, &row_generators);
+    let mut new_commitments = vec![Affine::<C>::identity(); commitments.len()];
+    if let Some(new_values) = new_values {
+        commit(&mut new_commitments, new_values, &row_generators);
+    }
+
+    let points: Vec<Projective<C>> = commitments
+        .par_iter()
+        .zip(&old_commitments)
+        .zip(&new_commitments)
+        .map(|((c, old), new)| c.into_group() - old + new)
+        .collect();
+    commitments.copy_from_slice(&Projective::normalize_batch(&points));
+    Ok(())
+}
+
+// Gets the generator at index `offset_generators + rows[k]` for each row `k`.
+//
+// The generators are fetched in ascending order, one call per run of rows whose
+// generators are at most `MAX_GENERATOR_GAP` apart, rather than one call per row.
+fn get_row_generators(
+    rows: &[u64],
+    offset_generators: u64,
+) -> Result<Vec<RistrettoPoint>, CommitmentError> {
+    let indices = rows
+        .iter()
+        .map(|&row| {
+            offset_generators
+                .checked_add(row)
+                .ok_or(CommitmentError::GeneratorIndexOverflow {
+                    row,
+                    offset_generators,
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut order: Vec<usize> = (0..rows.len()).collect();
+    order.sort_unstable_by_key(|&k| indices[k]);
+
+    let mut row_generators = vec![RistrettoPoint::default(); rows.len()];
+    let mut batch = Vec::new();
+    for run in order.chunk_by(|&j, &k| indices[k] - indices[j] <= MAX_GENERATOR_GAP) {
+        let first = indices[run[0]];
+        let last = indices[run[run.len() - 1]];
+        batch.resize((last - first + 1) as usize, RistrettoPoint::default());
+        get_curve25519_generators(&mut batch, first);
+        for &k in run {
+            row_generators[k] = batch[(indices[k] - first) as usize];
+        }
+    }
+    Ok(row_generators)
+}
+
+fn adjust_curve25519_commitments(
+    commitments: &mut [CompressedRistretto],
+    rows: &[u64],
+    old_values: &[Sequence],
+    new_values: Option<&[Sequence]>,
+    offset_generators: u64,
+) -> Result<(), CommitmentError> {
+    check_rows(commitments.len(), rows, old_values, new_values)?;
+    let row_generators = get_row_generators(rows, offset_generators)?;
+    let points = commitments
+        .par_iter()
+        .enumerate()
+        .map(|(index, c)| {
+            c.decompress()
+                .ok_or(CommitmentError::InvalidCommitment { operand: 0, index })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let commit = |values: &[Sequence]| {
+        let mut commitments = vec![CompressedRistretto::default(); values.len()];
+        compute_curve25519_commitments_with_generators(&mut commitments, values, &row_generators);
+        commitments
+    };
+    let old_commitments = commit(old_values);
+    let new_commitments = match new_values {
+        Some(new_values) => commit(new_values),
+        None => vec![CompressedRistretto::default(); commitments.len()],
+    };
+
+    commitments
+        .par_iter_mut()
+        .zip(points)
+        .zip(old_commitments.par_iter().zip(&new_commitments))
+        .for_each(|((c, point), (old, new))| {
+            let decompress = |c: &CompressedRistretto| {
+                c.decompress()
+                    .expect("the backend returns valid commitments")
+            };
+            *c = (point - decompress(old) + decompress(new)).compress();
+        });
+    Ok(())
+}
+
+/// Overwrites rows of the columns committed to by `commitments`.
+///
+/// Row `k` of the update is the row at generator index `offset_generators + rows[k]`.
+/// Column `i` held `old_values[i][k]` at that row and now holds `new_values[i][k]`,
+/// so the commitment of the difference `new_values[i] - old_values[i]` at those
+/// generators is added to `commitments[i]`. This gives the same commitments as
+/// recomputing them from the modified columns with [crate::compute::compute_curve25519_commitments],
+/// without committing to the unchanged rows.
+///
+/// # Example - Modify and Delete Rows
+///```no_run
+#[doc = include_str!("../../examples/modify_commitment_rows.rs")]
+///```
+///
+/// # Errors
+///
+/// If `old_values` or `new_values` does not have one column per commitment, a
+/// column does not have one value per row, a row is given more than once, the
+/// generator index `offset_generators + rows[k]` of a row overflows a `u64`, or a
+/// commitment is not a valid point.
+pub fn modify_curve25519_commitment_rows(
+    commitments: &mut [CompressedRistretto],
+    rows: &[u64],
+    old_values: &[Sequence],
+    new_values: &[Sequence],
+    offset_generators: u64,
+) -> Result<(), CommitmentError> {
+    adjust_curve25519_commitments(
+        commitments,
+        rows,
+        old_values,
+        Some(new_values),
+        offset_generators,
+    )
+}
+
+/// Deletes rows of the columns committed to by `commitments`.
+///
+/// The rows are set to zero: this is [modify_curve25519_commitment_rows] with new
+/// values of zero, so the commitment of `old_values[i]` at the generators of `rows`
+/// is subtracted from `commitments[i]`. Deleting the last rows of the columns gives
+/// the commitments of the shortened columns.
+///
+/// # Errors
+///
+/// See [modify_curve25519_commitment_rows].
+pub fn delete_curve25519_commitment_rows(
+    commitments: &mut [CompressedRistretto],
+    rows: &[u64],
+    old_values: &[Sequence],
+    offset_generators: u64,
+) -> Result<(), CommitmentError> {
+    adjust_curve25519_commitments(commitments, rows, old_values, None, offset_generators)
+}
+
+fn adjust_bls12_381_g1_commitments(
+    commitments: &mut [CompressedBls12381G1Commitment],
+    rows: &[u64],
+    old_values: &[Sequence],
+    new_values: Option<&[Sequence]>,
+    generators: &[ark_bls12_381::G1Affine],
+) -> Result<(), CommitmentError> {
+    let mut points: Vec<_> = commitments.iter().map(|c| c.decompress()).collect();
+    adjust_sw_commitments(
+        &mut points,
+        rows,
+        old_values,
+        new_values,
+        generators,
+        |res, values, generators| {
+            let mut bytes = vec![[0_u8; 48]; res.len()];
+            compute_bls12_381_g1_commitments_with_generators(&mut bytes, values, generators);
+            for (res, bytes) in res.iter_mut().zip(&bytes) {
+                *res = ark_bls12_381::G1Affine::deserialize_compressed(&bytes[..])
+                    .expect("the backend returns valid commitments");
+            }
+        },
+    )?;
+    for (c, point) in commitments.iter_mut().zip(&points) {
+        *c = point.into();
+    }
+    Ok(())
+}
+
+/// Overwrites rows of the columns committed to by `commitments`.
+///
+/// This is the same as [modify_curve25519_commitment_rows] for commitments computed
+/// with [crate::compute::compute_bls12_381_g1_commitments_with_generators]: row `k` of
+/// the update uses the generator `generators[rows[k]]`.
+///
+/// # Errors
+///
+/// If `old_values` or `new_values` does not have one column per commitment, a
+/// column does not have one value per row, a row is given more than once, or a
+/// row is out of the range of `generators`.
+pub fn modify_bls12_381_g1_commitment_rows(
+    commitments: &mut [CompressedBls12381G1Commitment],
+    rows: &[u64],
+    old_values: &[Sequence],
+    new_values: &[Sequence],
+    generators: &[ark_bls12_381::G1Affine],
+) -> Result<(), CommitmentError> {
+    adjust_bls12_381_g1_commitments(commitments, rows, old_values, Some(new_values), generators)
+}
+
+/// Deletes rows of the columns committed to by `commitments`, see
+/// [delete_curve25519_commitment_rows] and [modify_bls12_381_g1_commitment_rows].
+pub fn delete_bls12_381_g1_commitment_rows(
+    commitments: &mut [CompressedBls12381G1Commitment],
+    rows: &[u64],
+    old_values: &[Sequence],
+    generators: &[ark_bls12_381::G1Affine],
+) -> Result<(), CommitmentError> {
+    adjust_bls12_381_g1_commitments(commitments, rows, old_values, None, generators)
+}
+
+/// Overwrites rows of the columns committed to by `commitments`.
+///
+/// This is the same as [modify_curve25519_commitment_rows] for commitments computed
+/// with [crate::compute::compute_bn254_g1_uncompressed_commitments_with_generators]:
+/// row `k` of the update uses the generator `generators[rows[k]]`.
+///
+/// # Errors
+///
+/// See [modify_bls12_381_g1_commitment_rows].
+pub fn modify_bn254_g1_uncompressed_commitment_rows(
+    commitments: &mut [ark_bn254::G1Affine],
+    rows: &[u64],
+    old_values: &[Sequence],
+    new_values: &[Sequence],
+    generators: &[ark_bn254::G1Affine],
+) -> Result<(), CommitmentError> {
+    adjust_sw_commitments(
+        commitments,
+        rows,
+        old_values,
+        Some(new_values),
+        generators,
+        compute_bn254_g1_uncompressed_commitments_with_generators,
+    )
+}
+
+/// Deletes rows of the columns committed to by `commitments`, see
+/// [delete_curve25519_commitment_rows] and [modify_bn254_g1_uncompressed_commitment_rows].
+pub fn delete_bn254_g1_uncompressed_commitment_rows(
+    commitments: &mut [ark_bn254::G1Affine],
+    rows: &[u64],
+    old_values: &[Sequence],
+    generators: &[ark_bn254::G1Affine],
+) -> Result<(), CommitmentError> {
+    adjust_sw_commitments(
+        commitments,
+        rows,
+        old_values,
+        None,
+        generators,
+        compute_bn254_g1_uncompressed_commitments_with_generators,
+    )
+}
+
+/// Overwrites rows of the columns committed to by `commitments`.
+///
+/// This is the same as [modify_curve25519_commitment_rows] for commitments computed
+/// with [crate::compute::compute_grumpkin_uncompressed_commitments_with_generators]:
+/// row `k` of the update uses the generator `generators[rows[k]]`.
+///
+/// # Errors
+///
+/// See [modify_bls12_381_g1_commitment_rows].
+pub fn modify_grumpkin_uncompressed_commitment_rows(
+    commitments: &mut [GrumpkinAffine],
+    rows: &[u64],
+    old_values: &[Sequence],
+    new_values: &[Sequence],
+    generators: &[GrumpkinAffine],
+) -> Result<(), CommitmentError> {
+    adjust_sw_commitments(
+        commitments,
+        rows,
+        old_values,
+        Some(new_values),
+        generators,
+        compute_grumpkin_uncompressed_commitments_with_generators,
+    )
+}
+
+/// Deletes rows of the columns committed to by `commitments`, see
+/// [delete_curve25519_commitment_rows] and [modify_grumpkin_uncompressed_commitment_rows].
+pub fn delete_grumpkin_uncompressed_commitment_rows(
+    commitments: &mut [GrumpkinAffine],
+    rows: &[u64],
+    old_values: &[Sequence],
+    generators: &[GrumpkinAffine],
+) -> Result<(), CommitmentError> {
+    adjust_sw_commitments(
+        commitments,
+        rows,
+        old_values,
+        None,
+        generators,
+        compute_grumpkin_uncompressed_commitments_with_generators,
+    )
+}
@@ -0,0 +1,270 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use ark_std::UniformRand;
+use curve25519_dalek::ristretto::CompressedRistretto;
+
+#[test]
+fn we_can_modify_and_delete_curve25519_commitment_rows() {
+    let data: Vec<i64> = vec![1, -2, 3, 4, 5];
+    let mut commitments = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut commitments, &[(&data).into()], 7);
+
+    let old_values: Vec<i64> = vec![-2, 5];
+    let new_values: Vec<i64> = vec![-20, 50];
+    modify_curve25519_commitment_rows(
+        &mut commitments,
+        &[1, 4],
+        &[(&old_values).into()],
+        &[(&new_values).into()],
+        7,
+    )
+    .unwrap();
+
+    let modified: Vec<i64> = vec![1, -20, 3, 4, 50];
+    let mut expected = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut expected, &[(&modified).into()], 7);
+    assert_eq!(commitments, expected);
+
+    let old_values: Vec<i64> = vec![4, 50];
+    delete_curve25519_commitment_rows(&mut commitments, &[3, 4], &[(&old_values).into()], 7)
+        .unwrap();
+
+    let shortened: Vec<i64> = vec![1, -20, 3];
+    compute_curve25519_commitments(&mut expected, &[(&shortened).into()], 7);
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+fn we_can_modify_unordered_curve25519_commitment_rows_far_apart() {
+    let mut data: Vec<i64> = (0..200).collect();
+    let mut commitments = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut commitments, &[(&data).into()], 3);
+
+    let rows: Vec<u64> = vec![150, 2, 40, 3, 199];
+    let old_values: Vec<i64> = rows.iter().map(|&row| row as i64).collect();
+    let new_values: Vec<i64> = vec![-1, -2, -3, -4, -5];
+    modify_curve25519_commitment_rows(
+        &mut commitments,
+        &rows,
+        &[(&old_values).into()],
+        &[(&new_values).into()],
+        3,
+    )
+    .unwrap();
+
+    for (&row, &value) in rows.iter().zip(&new_values) {
+        data[row as usize] = value;
+    }
+    let mut expected = vec![CompressedRistretto::default(); 1];
+    compute_curve25519_commitments(&mut expected, &[(&data).into()], 3);
+    assert_eq!(commitments, expected);
+}
+
+#[test]
+fn modifying_duplicate_or_overflowing_rows_is_an_error() {
+    let values: Vec<u32> = vec![1, 2, 3];
+    let mut commitments = vec![CompressedRistretto::default(); 1];
+    assert_eq!(
+        delete_curve25519_commitment_rows(&mut commitments, &[4, 1, 4], &[(&values).into()], 0),
+        Err(CommitmentError::DuplicateRow { row: 4 })
+    );
+    assert_eq!(
+        delete_curve25519_commitment_rows(
+            &mut commitments,
+            &[0, 1, u64::MAX],
+            &[(&values).into()],
+            1,
+        ),
+        Err(CommitmentError::GeneratorIndexOverflow {
+            row: u64::MAX,
+            offset_generators: 1
+        })
+    );
+
+    let generators = vec![GrumpkinAffine::default(); 2];
+    let mut commitments = vec![GrumpkinAffine::default(); 1];
+    assert_eq!(
+        delete_grumpkin_uncompressed_commitment_rows(
+            &mut commitments,
+            &[1, 1],
+            &[(&values[..2]).into()],
+            &generators,
+        ),
+        Err(CommitmentError::DuplicateRow { row: 1 })
+    );
+}
+
+#[test]
+fn modifying_curve25519_commitment_rows_reports_errors() {
+    let values: Vec<u32> = vec![1, 2];
+    let mut commitments = vec![CompressedRistretto::default(); 2];
+
+    assert_eq!(
+        modify_curve25519_commitment_rows(
+            &mut commitments,
+            &[0, 1],
+            &[(&values).into()],
+            &[(&values).into(), (&values).into()],
+            0,
+        ),
+        Err(CommitmentError::LengthMismatch {
+            expected: 2,
+            found: 1
+        })
+    );
+    assert_eq!(
+        delete_curve25519_commitment_rows(
+            &mut commitments,
+            &[0],
+            &[(&values).into(), (&values).into()],
+            0,
+        ),
+        Err(CommitmentError::LengthMismatch {
+            expected: 1,
+            found: 2
+        })
+    );
+
+    commitments[1] = CompressedRistretto([0xff; 32]);
+    assert_eq!(
+        delete_curve25519_commitment_rows(
+            &mut commitments,
+            &[0, 1],
+            &[(&values).into(), (&values).into()],
+            0,
+        ),
+        Err(CommitmentError::InvalidCommitment {
+            operand: 0,
+            index: 1
+        })
+    );
+}
+
+#[test]
+fn we_can_modify_and_delete_short_weierstrass_commitment_rows() {
+    let mut rng = ark_std::test_rng();
+    let data: Vec<u64> = vec![3, 1, 4, 1, 5];
+    let modified: Vec<u64> = vec![3, 9, 4, 1, 2];
+    let shortened: Vec<u64> = vec![3, 9, 4];
+    let old_values: Vec<u64> = vec![1, 5];
+    let new_values: Vec<u64> = vec![9, 2];
+    let deleted_values: Vec<u64> = vec![1, 2];
+
+    let generators: Vec<ark_bls12_381::G1Affine> = (0..5)
+        .map(|_| ark_bls12_381::G1Affine::rand(&mut rng))
+        .collect();
+    let commit = |data: &Vec<u64>| {
+        let mut bytes = vec![[0_u8; 48]; 1];
+        compute_bls12_381_g1_commitments_with_generators(&mut bytes, &[data.into()], &generators);
+        CompressedBls12381G1Commitment::from_bytes(bytes[0]).unwrap()
+    };
+    let mut commitments = vec![commit(&data)];
+    modify_bls12_381_g1_commitment_rows(
+        &mut commitments,
+        &[1, 4],
+        &[(&old_values).into()],
+        &[(&new_values).into()],
+        &generators,
+    )
+    .unwrap();
+    assert_eq!(commitments[0], commit(&modified));
+    delete_bls12_381_g1_commitment_rows(
+        &mut commitments,
+        &[3, 4],
+        &[(&deleted_values).into()],
+        &generators,
+    )
+    .unwrap();
+    assert_eq!(commitments[0], commit(&shortened));
+
+    let generators: Vec<ark_bn254::G1Affine> = (0..5)
+        .map(|_| ark_bn254::G1Affine::rand(&mut rng))
+        .collect();
+    let commit = |data: &Vec<u64>| {
+        let mut commitments = vec![ark_bn254::G1Affine::default(); 1];
+        compute_bn254_g1_uncompressed_commitments_with_generators(
+            &mut commitments,
+            &[data.into()],
+            &generators,
+        );
+        commitments
+    };
+    let mut commitments = commit(&data);
+    modify_bn254_g1_uncompressed_commitment_rows(
+        &mut commitments,
+        &[1, 4],
+        &[(&old_values).into()],
+        &[(&new_values).into()],
+        &generators,
+    )
+    .unwrap();
+    assert_eq!(commitments, commit(&modified));
+    delete_bn254_g1_uncompressed_commitment_rows(
+        &mut commitments,
+        &[3, 4],
+        &[(&deleted_values).into()],
+        &generators,
+    )
+    .unwrap();
+    assert_eq!(commitments, commit(&shortened));
+
+    let generators: Vec<GrumpkinAffine> = (0..5).map(|_| GrumpkinAffine::rand(&mut rng)).collect();
+    let commit = |data: &Vec<u64>| {
+        let mut commitments = vec![GrumpkinAffine::default(); 1];
+        compute_grumpkin_uncompressed_commitments_with_generators(
+            &mut commitments,
+            &[data.into()],
+            &generators,
+        );
+        commitments
+    };
+    let mut commitments = commit(&data);
+    modify_grumpkin_uncompressed_commitment_rows(
+        &mut commitments,
+        &[1, 4],
+        &[(&old_values).into()],
+        &[(&new_values).into()],
+        &generators,
+    )
+    .unwrap();
+    assert_eq!(commitments, commit(&modified));
+    delete_grumpkin_uncompressed_commitment_rows(
+        &mut commitments,
+        &[3, 4],
+        &[(&deleted_values).into()],
+        &generators,
+    )
+    .unwrap();
+    assert_eq!(commitments, commit(&shortened));
+}
+
+#[test]
+fn modifying_rows_without_generators_is_an_error() {
+    let values: Vec<u32> = vec![1];
+    let generators = vec![GrumpkinAffine::default(); 2];
+    let mut commitments = vec![GrumpkinAffine::default(); 1];
+    assert_eq!(
+        delete_grumpkin_uncompressed_commitment_rows(
+            &mut commitments,
+            &[2],
+            &[(&values).into()],
+            &generators,
+        ),
+        Err(CommitmentError::RowOutOfRange {
+            row: 2,
+            num_generators: 2
+        })
+    );
+}
@@ -83,6 +83,14 @@
 //!  cargo bench --features gpu
 //! ```
 
+#[cfg(not(any(
+    feature = "cpu",
+    feature = "gpu",
+    feature = "reference",
+    feature = "verifier"
+)))]
+compile_error!("one of the `cpu`, `gpu`, `reference` or `verifier` features must be enabled");
+
 //------------------------------------------------------------------------
 // blitzar public modules
 //------------------------------------------------------------------------
@@ -11,6 +11,7 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use crate::compute::MsmError;
 use thiserror::Error;
 
 /// ProofError related to the proof primitives
@@ -19,4 +20,20 @@ pub enum ProofError {
     /// This error occurs when a proof failed to verify.
     #[error("Verification error")]
     VerificationError,
+
+    /// This error occurs when a structured reference string is malformed.
+    #[error("Invalid structured reference string")]
+    InvalidSrs,
+
+    /// This error occurs when a polynomial has a larger degree than the setup supports.
+    #[error("Polynomial degree exceeds the maximum degree of the setup")]
+    DegreeTooLarge,
+
+    /// This error occurs when reading or writing a setup file fails.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// This error occurs when a multi-scalar multiplication fails.
+    #[error("MSM error: {0}")]
+    Msm(#[from] MsmError),
 }
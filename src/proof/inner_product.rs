@@ -11,15 +11,23 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-use super::error::ProofError;
-use crate::compute::init_backend;
+use super::{error::ProofError, inner_product_host, transcript::Transcript};
+use crate::compute::get_curve25519_generators;
+#[cfg(feature = "blitzar-sys")]
+use crate::compute::use_reference;
 use curve25519_dalek::{
     ristretto::{CompressedRistretto, RistrettoPoint},
     scalar::Scalar,
 };
-use merlin::Transcript;
 use serde::{Deserialize, Serialize};
 
+/// Returns the `np + 1` generators used by proofs of length `n`.
+fn get_proof_generators(n: usize, generators_offset: u64) -> Vec<RistrettoPoint> {
+    let mut generators = vec![RistrettoPoint::default(); n.next_power_of_two() + 1];
+    get_curve25519_generators(&mut generators, generators_offset);
+    generators
+}
+
 /// InnerProductProof construct
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct InnerProductProof {
@@ -75,7 +83,7 @@ impl InnerProductProof {
     /// and the verifier responds with a
     /// challenge value `u[j]` <- `Z_p` (finite field of order `p`),
     /// which is non-interactively simulated by
-    /// the input strobe-based transcript.
+    /// the input transcript.
     ///
     /// ```text
     /// transcript.append("L", l_vector[j]);
@@ -103,25 +111,48 @@ impl InnerProductProof {
     ///
     /// After the last (`j = 0`) round, the prover sends `ap_value = a[0]` to the verifier.
     ///
+    /// The challenge `u[j]` is obtained by drawing 32 challenge bytes and reducing
+    /// them modulo the group order. When `transcript` is a `merlin` transcript the
+    /// proof is computed by the backend; otherwise, with the reference backend, or
+    /// without blitzar-sys, it is computed on the host.
+    ///
     /// # Arguments:
     ///
-    /// - `transcript` (in/out): a single strobe-based transcript
+    /// - `transcript` (in/out): a single transcript implementing [Transcript]
     /// - `a` (in): array with non-zero length `n`
     /// - `b` (in): array with non-zero length `n`
     /// - `generators_offset` (in): offset used to fetch the bases
-    pub fn create(
-        transcript: &mut Transcript,
+    pub fn create<T: Transcript + ?Sized>(
+        transcript: &mut T,
         a: &[Scalar],
         b: &[Scalar],
         generators_offset: u64,
     ) -> InnerProductProof {
-        init_backend();
-
         let n: u64 = a.len() as u64;
 
         assert!(n > 0);
         assert!(n == b.len() as u64);
 
+        #[cfg(feature = "blitzar-sys")]
+        if !use_reference() {
+            if let Some(transcript) = transcript.as_merlin_transcript() {
+                return Self::create_with_backend(transcript, a, b, generators_offset);
+            }
+        }
+
+        let generators = get_proof_generators(a.len(), generators_offset);
+        inner_product_host::create(transcript, a, b, &generators)
+    }
+
+    // Creates a proof for a merlin transcript with blitzar-sys.
+    #[cfg(feature = "blitzar-sys")]
+    fn create_with_backend(
+        transcript: &mut merlin::Transcript,
+        a: &[Scalar],
+        b: &[Scalar],
+        generators_offset: u64,
+    ) -> InnerProductProof {
+        let n: u64 = a.len() as u64;
         let ceil_lg2_n = n.next_power_of_two().trailing_zeros() as usize;
         let mut ap_value = Scalar::default();
         let mut l_vector: Vec<CompressedRistretto> =
@@ -132,7 +163,8 @@ impl InnerProductProof {
         unsafe {
             let a = a.as_ptr() as *const blitzar_sys::sxt_curve25519_scalar;
             let b = b.as_ptr() as *const blitzar_sys::sxt_curve25519_scalar;
-            let transcript = transcript as *mut Transcript as *mut blitzar_sys::sxt_transcript;
+            let transcript =
+                transcript as *mut merlin::Transcript as *mut blitzar_sys::sxt_transcript;
 
             let ap_value = &mut ap_value as *mut Scalar as *mut blitzar_sys::sxt_curve25519_scalar;
             let l_vector = l_vector.as_mut_ptr() as *mut blitzar_sys::sxt_ristretto255_compressed;
@@ -170,9 +202,12 @@ impl InnerProductProof {
     /// Note that we don't have any restriction to the `n` value, other than
     /// it has to be non-zero.
     ///
+    /// Without blitzar-sys, as with the `verifier` feature alone, the proof is
+    /// verified on the host with the pure-Rust generator derivation.
+    ///
     /// # Arguments:
     ///
-    /// - `transcript` (in/out): a single strobe-based transcript
+    /// - `transcript` (in/out): a single transcript implementing [Transcript]
     /// - `a_commit` (in): a single Ristretto point,
     ///                  represented by `<a, G>` (the inner product of the two vectors)
     /// - `product` (in): a single scalar, represented by `<a, b>`,
@@ -180,16 +215,14 @@ impl InnerProductProof {
     ///                 `InnerProductProof::create(...)`
     /// - `b` (in): array with non-zero length `n`, the same one used by `InnerProductProof::create(...)`
     /// - `generators_offset` (in): offset used to fetch the bases
-    pub fn verify(
+    pub fn verify<T: Transcript + ?Sized>(
         &self,
-        transcript: &mut Transcript,
+        transcript: &mut T,
         a_commit: &RistrettoPoint,
         product: &Scalar,
         b: &[Scalar],
         generators_offset: u64,
     ) -> Result<(), ProofError> {
-        init_backend();
-
         let n = b.len();
         assert!(n > 0);
 
@@ -199,7 +232,88 @@ impl InnerProductProof {
             return Err(ProofError::VerificationError);
         }
 
-        let transcript = transcript as *mut Transcript as *mut blitzar_sys::sxt_transcript;
+        #[cfg(feature = "blitzar-sys")]
+        if !use_reference() {
+            if let Some(transcript) = transcript.as_merlin_transcript() {
+                return self.verify_with_backend(
+                    transcript,
+                    a_commit,
+                    product,
+                    b,
+                    generators_offset,
+                );
+            }
+        }
+
+        let generators = get_proof_generators(n, generators_offset);
+        inner_product_host::verify(self, transcript, a_commit, product, b, &generators)
+    }
+
+    /// Creates an inner product proof with respect to the given bases.
+    ///
+    /// This is the same as [InnerProductProof::create], except that `G` is
+    /// `generators` instead of the backend generators, such as a generator set from
+    /// [crate::compute::derive_labeled_curve25519_generators]. The proof is always
+    /// computed on the host.
+    ///
+    /// # Panics
+    ///
+    /// If `a` is empty, `b` has a different length than `a`, or `generators` does not
+    /// hold exactly `np + 1` points, where `np = 1ull << ceil(log2(n))`.
+    pub fn create_with_generators<T: Transcript + ?Sized>(
+        transcript: &mut T,
+        a: &[Scalar],
+        b: &[Scalar],
+        generators: &[RistrettoPoint],
+    ) -> InnerProductProof {
+        assert!(!a.is_empty());
+        assert_eq!(a.len(), b.len());
+
+        inner_product_host::create(transcript, a, b, generators)
+    }
+
+    /// Verifies an inner product proof with respect to the given bases.
+    ///
+    /// This is the same as [InnerProductProof::verify], except that `G` is
+    /// `generators`, which must be the bases used by
+    /// [InnerProductProof::create_with_generators]. The proof is always verified on
+    /// the host.
+    ///
+    /// Returns [ProofError::VerificationError] if `generators` does not hold exactly
+    /// `np + 1` points.
+    ///
+    /// # Panics
+    ///
+    /// If `b` is empty.
+    pub fn verify_with_generators<T: Transcript + ?Sized>(
+        &self,
+        transcript: &mut T,
+        a_commit: &RistrettoPoint,
+        product: &Scalar,
+        b: &[Scalar],
+        generators: &[RistrettoPoint],
+    ) -> Result<(), ProofError> {
+        assert!(!b.is_empty());
+
+        if generators.len() != b.len().next_power_of_two() + 1 {
+            return Err(ProofError::VerificationError);
+        }
+
+        inner_product_host::verify(self, transcript, a_commit, product, b, generators)
+    }
+
+    // Verifies a proof for a merlin transcript with blitzar-sys.
+    #[cfg(feature = "blitzar-sys")]
+    fn verify_with_backend(
+        &self,
+        transcript: &mut merlin::Transcript,
+        a_commit: &RistrettoPoint,
+        product: &Scalar,
+        b: &[Scalar],
+        generators_offset: u64,
+    ) -> Result<(), ProofError> {
+        let n = b.len();
+        let transcript = transcript as *mut merlin::Transcript as *mut blitzar_sys::sxt_transcript;
         let b = b.as_ptr() as *const blitzar_sys::sxt_curve25519_scalar;
         let product = product as *const Scalar as *const blitzar_sys::sxt_curve25519_scalar;
         let a_commit = a_commit as *const RistrettoPoint as *const blitzar_sys::sxt_ristretto255;
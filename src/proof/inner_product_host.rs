@@ -0,0 +1,181 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Host implementation of the inner product argument.
+//!
+//! This follows exactly the protocol described in [super::InnerProductProof]
+//! and is used for transcripts that the backend cannot drive.
+
+use super::{error::ProofError, inner_product::InnerProductProof, transcript::Transcript};
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::VartimeMultiscalarMul,
+};
+
+fn init_transcript<T: Transcript + ?Sized>(transcript: &mut T, n: u64) {
+    transcript.append_message(b"domain-sep", b"inner product proof v1");
+    transcript.append_u64(b"n", n);
+}
+
+fn inner_product(a: &[Scalar], b: &[Scalar]) -> Scalar {
+    a.iter().zip(b).map(|(a_i, b_i)| a_i * b_i).sum()
+}
+
+/// Creates an inner product proof.
+///
+/// `generators` must hold `np + 1` points, where `np` is the smallest power of
+/// two greater than or equal to `a.len()`.
+pub(super) fn create<T: Transcript + ?Sized>(
+    transcript: &mut T,
+    a: &[Scalar],
+    b: &[Scalar],
+    generators: &[RistrettoPoint],
+) -> InnerProductProof {
+    let n = a.len();
+    let np = n.next_power_of_two();
+    assert_eq!(generators.len(), np + 1);
+
+    init_transcript(transcript, n as u64);
+
+    let q = generators[np];
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    let mut g = generators[..np].to_vec();
+    a.resize(np, Scalar::ZERO);
+    b.resize(np, Scalar::ZERO);
+
+    let num_rounds = np.trailing_zeros() as usize;
+    let mut l_vector = Vec::with_capacity(num_rounds);
+    let mut r_vector = Vec::with_capacity(num_rounds);
+
+    while a.len() > 1 {
+        let half = a.len() / 2;
+        let (a_lo, a_hi) = a.split_at(half);
+        let (b_lo, b_hi) = b.split_at(half);
+        let (g_lo, g_hi) = g.split_at(half);
+
+        let l_value = RistrettoPoint::vartime_multiscalar_mul(
+            a_lo.iter()
+                .chain(std::iter::once(&inner_product(a_lo, b_hi))),
+            g_hi.iter().chain(std::iter::once(&q)),
+        )
+        .compress();
+        let r_value = RistrettoPoint::vartime_multiscalar_mul(
+            a_hi.iter()
+                .chain(std::iter::once(&inner_product(a_hi, b_lo))),
+            g_lo.iter().chain(std::iter::once(&q)),
+        )
+        .compress();
+
+        transcript.append_message(b"L", l_value.as_bytes());
+        transcript.append_message(b"R", r_value.as_bytes());
+        let u = transcript.challenge_scalar(b"x");
+        let u_inv = u.invert();
+
+        a = (0..half).map(|i| a_lo[i] * u + u_inv * a_hi[i]).collect();
+        b = (0..half).map(|i| b_lo[i] * u_inv + u * b_hi[i]).collect();
+        g = (0..half).map(|i| g_lo[i] * u_inv + g_hi[i] * u).collect();
+
+        l_vector.push(l_value);
+        r_vector.push(r_value);
+    }
+
+    InnerProductProof {
+        l_vector,
+        r_vector,
+        ap_value: a[0],
+    }
+}
+
+/// Verifies an inner product proof.
+///
+/// `generators` must hold `np + 1` points, where `np` is the smallest power of
+/// two greater than or equal to `b.len()`.
+pub(super) fn verify<T: Transcript + ?Sized>(
+    proof: &InnerProductProof,
+    transcript: &mut T,
+    a_commit: &RistrettoPoint,
+    product: &Scalar,
+    b: &[Scalar],
+    generators: &[RistrettoPoint],
+) -> Result<(), ProofError> {
+    let n = b.len();
+    let np = n.next_power_of_two();
+    let num_rounds = np.trailing_zeros() as usize;
+    assert_eq!(generators.len(), np + 1);
+
+    if proof.l_vector.len() != num_rounds || proof.r_vector.len() != num_rounds {
+        return Err(ProofError::VerificationError);
+    }
+
+    init_transcript(transcript, n as u64);
+
+    let mut u = Vec::with_capacity(num_rounds);
+    for (l_value, r_value) in proof.l_vector.iter().zip(&proof.r_vector) {
+        transcript.append_message(b"L", l_value.as_bytes());
+        transcript.append_message(b"R", r_value.as_bytes());
+        u.push(transcript.challenge_scalar(b"x"));
+    }
+    let u_inv: Vec<Scalar> = u.iter().map(Scalar::invert).collect();
+
+    // s[i] is the coefficient of the i-th generator after all folding rounds.
+    // The first round splits on the highest bit of i.
+    let s: Vec<Scalar> = (0..np)
+        .map(|i| {
+            (0..num_rounds)
+                .map(|j| {
+                    if (i >> (num_rounds - 1 - j)) & 1 == 1 {
+                        u[j]
+                    } else {
+                        u_inv[j]
+                    }
+                })
+                .product()
+        })
+        .collect();
+    let b_final: Scalar = s.iter().zip(b).map(|(s_i, b_i)| s_i * b_i).sum();
+
+    let decompress = |c: &CompressedRistretto| c.decompress().ok_or(ProofError::VerificationError);
+    let l_points = proof
+        .l_vector
+        .iter()
+        .map(decompress)
+        .collect::<Result<Vec<_>, _>>()?;
+    let r_points = proof
+        .r_vector
+        .iter()
+        .map(decompress)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // a_commit + product * Q + sum_j (u_j^2 * L_j + u_j^-2 * R_j)
+    //     == ap * <s, G> + ap * <s, b> * Q
+    let ap = proof.ap_value;
+    let scalars = std::iter::once(Scalar::ONE)
+        .chain(std::iter::once(product - ap * b_final))
+        .chain(u.iter().map(|u_j| u_j * u_j))
+        .chain(u_inv.iter().map(|u_j| u_j * u_j))
+        .chain(s.iter().map(|s_i| -(ap * s_i)));
+    let points = std::iter::once(*a_commit)
+        .chain(std::iter::once(generators[np]))
+        .chain(l_points)
+        .chain(r_points)
+        .chain(generators[..np].iter().copied());
+
+    if RistrettoPoint::vartime_multiscalar_mul(scalars, points) == RistrettoPoint::default() {
+        Ok(())
+    } else {
+        Err(ProofError::VerificationError)
+    }
+}
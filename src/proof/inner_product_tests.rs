@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use super::*;
-use crate::compute::get_curve25519_generators;
+use crate::compute::{derive_labeled_curve25519_generators, get_curve25519_generators};
 use core::{mem, slice};
 use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
 use merlin::Transcript;
@@ -187,3 +187,235 @@ fn test_prove_and_verify_random_proofs_of_varying_size() {
         test_prove_and_verify_with_given_n_and_generators_offset(i, i);
     }
 }
+
+/// Hides the inner merlin transcript, forcing proofs to be computed on the host.
+struct HostTranscript(Transcript);
+
+impl crate::proof::Transcript for HostTranscript {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.0.append_message(label, message);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.0.challenge_bytes(label, dest);
+    }
+}
+
+fn test_prove_and_verify_on_the_host_with_given_n(n: u64, generators_offset: u64) {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(n);
+    let a: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+    let b: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+    let mut g = vec![RistrettoPoint::default(); n as usize];
+    get_curve25519_generators(&mut g, generators_offset);
+    let product = a.iter().zip(&b).map(|(a_i, b_i)| a_i * b_i).sum::<Scalar>();
+    let a_commit = a
+        .iter()
+        .zip(&g)
+        .map(|(a_i, g_i)| a_i * g_i)
+        .sum::<RistrettoPoint>();
+
+    let mut transcript = HostTranscript(Transcript::new(b"innerproducttest"));
+    let proof = InnerProductProof::create(&mut transcript, &a, &b, generators_offset);
+
+    // We can verify a proof using a valid input data
+    let mut transcript = HostTranscript(Transcript::new(b"innerproducttest"));
+    assert!(proof
+        .verify(&mut transcript, &a_commit, &product, &b, generators_offset)
+        .is_ok());
+
+    // We cannot verify a proof using an invalid product
+    let mut transcript = HostTranscript(Transcript::new(b"innerproducttest"));
+    let product_p = product + Scalar::from(123_u64);
+    assert!(proof
+        .verify(
+            &mut transcript,
+            &a_commit,
+            &product_p,
+            &b,
+            generators_offset
+        )
+        .is_err());
+
+    // We cannot verify a proof using an invalid transcript
+    if n > 1 {
+        let mut transcript = HostTranscript(Transcript::new(b"invalid"));
+        assert!(proof
+            .verify(&mut transcript, &a_commit, &product, &b, generators_offset)
+            .is_err());
+    }
+}
+
+#[test]
+fn we_can_prove_and_verify_on_the_host_with_a_non_merlin_transcript() {
+    for n in 1_u64..10_u64 {
+        test_prove_and_verify_on_the_host_with_given_n(n, 0);
+        test_prove_and_verify_on_the_host_with_given_n(n, n);
+    }
+}
+
+#[test]
+fn host_and_backend_proofs_are_identical_for_the_same_transcript() {
+    for n in 1_u64..10_u64 {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(n);
+        let a: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+        let b: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+
+        let mut transcript = Transcript::new(b"innerproducttest");
+        let backend_proof = InnerProductProof::create(&mut transcript, &a, &b, n);
+        let mut transcript = HostTranscript(Transcript::new(b"innerproducttest"));
+        let host_proof = InnerProductProof::create(&mut transcript, &a, &b, n);
+
+        assert_eq!(backend_proof.l_vector, host_proof.l_vector);
+        assert_eq!(backend_proof.r_vector, host_proof.r_vector);
+        assert_eq!(backend_proof.ap_value, host_proof.ap_value);
+    }
+}
+
+#[test]
+fn we_can_verify_a_deserialized_proof() {
+    let n = 5_u64;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(n);
+    let a: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+    let b: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+    let mut g = vec![RistrettoPoint::default(); n as usize];
+    get_curve25519_generators(&mut g, 3);
+    let product = a.iter().zip(&b).map(|(a_i, b_i)| a_i * b_i).sum::<Scalar>();
+    let a_commit = a
+        .iter()
+        .zip(&g)
+        .map(|(a_i, g_i)| a_i * g_i)
+        .sum::<RistrettoPoint>();
+
+    let mut transcript = Transcript::new(b"innerproducttest");
+    let proof = InnerProductProof::create(&mut transcript, &a, &b, 3);
+    let serialized = serde_json::to_string(&proof).unwrap();
+    let proof: InnerProductProof = serde_json::from_str(&serialized).unwrap();
+
+    let mut transcript = Transcript::new(b"innerproducttest");
+    assert!(proof
+        .verify(&mut transcript, &a_commit, &product, &b, 3)
+        .is_ok());
+}
+
+#[test]
+fn we_can_prove_and_verify_with_labeled_generators() {
+    for n in 1_u64..10_u64 {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(n);
+        let a: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+        let b: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+        let np = n.next_power_of_two() as usize;
+        let mut generators = vec![RistrettoPoint::default(); np + 1];
+        derive_labeled_curve25519_generators(b"test", &mut generators, 0);
+        let product = a.iter().zip(&b).map(|(a_i, b_i)| a_i * b_i).sum::<Scalar>();
+        let a_commit = a
+            .iter()
+            .zip(&generators)
+            .map(|(a_i, g_i)| a_i * g_i)
+            .sum::<RistrettoPoint>();
+
+        let mut transcript = Transcript::new(b"innerproducttest");
+        let proof = InnerProductProof::create_with_generators(&mut transcript, &a, &b, &generators);
+
+        let mut transcript = Transcript::new(b"innerproducttest");
+        assert!(proof
+            .verify_with_generators(&mut transcript, &a_commit, &product, &b, &generators)
+            .is_ok());
+
+        // We cannot verify a proof with the generators of another label
+        let mut other_generators = vec![RistrettoPoint::default(); np + 1];
+        derive_labeled_curve25519_generators(b"other", &mut other_generators, 0);
+        let mut transcript = Transcript::new(b"innerproducttest");
+        assert!(proof
+            .verify_with_generators(&mut transcript, &a_commit, &product, &b, &other_generators)
+            .is_err());
+    }
+}
+
+#[test]
+fn we_cannot_verify_with_the_wrong_number_of_generators() {
+    let n = 3_u64;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(n);
+    let a: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+    let b: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+    let mut generators = vec![RistrettoPoint::default(); 6];
+    derive_labeled_curve25519_generators(b"test", &mut generators, 0);
+    let product = a.iter().zip(&b).map(|(a_i, b_i)| a_i * b_i).sum::<Scalar>();
+    let a_commit = a
+        .iter()
+        .zip(&generators)
+        .map(|(a_i, g_i)| a_i * g_i)
+        .sum::<RistrettoPoint>();
+
+    let mut transcript = Transcript::new(b"innerproducttest");
+    let proof =
+        InnerProductProof::create_with_generators(&mut transcript, &a, &b, &generators[..5]);
+
+    for generators in [&generators[..4], &generators[..6], &[]] {
+        let mut transcript = Transcript::new(b"innerproducttest");
+        assert!(matches!(
+            proof.verify_with_generators(&mut transcript, &a_commit, &product, &b, generators),
+            Err(ProofError::VerificationError)
+        ));
+    }
+}
+
+// A proof of `a = [1, 2, 3, 4, 5]` and `b = [6, 7, 8, 9, 10]` for the transcript
+// label `innerproductknownanswer` and generators offset 3. The native backend must
+// reproduce it, which is checked by the cpu and gpu test jobs.
+const KNOWN_ANSWER_PROOF: &str = r#"{
+    "l_vector": [
+        [202,231,76,175,119,64,169,114,33,1,37,59,102,211,242,116,1,255,56,150,253,77,183,74,122,98,138,184,224,97,45,28],
+        [236,98,197,255,80,247,200,41,215,121,142,229,75,35,33,100,99,194,49,28,77,18,226,181,57,98,246,232,120,88,221,15],
+        [26,4,233,86,166,194,35,200,30,244,192,243,156,98,42,47,165,4,121,229,233,62,255,133,152,114,217,12,243,134,106,79]
+    ],
+    "r_vector": [
+        [152,214,63,90,8,173,237,123,234,113,226,29,120,209,204,83,191,244,80,135,34,171,1,202,36,131,167,115,164,173,191,58],
+        [56,243,200,212,252,238,103,124,95,96,250,59,33,176,35,18,15,149,228,123,6,152,94,40,219,131,62,197,105,107,178,9],
+        [170,156,2,16,143,98,206,249,219,246,51,208,207,178,105,24,85,76,182,23,247,188,186,149,9,177,92,85,102,166,21,70]
+    ],
+    "ap_value": [88,17,199,26,135,34,227,139,171,209,31,42,175,147,106,3,250,248,17,148,92,63,4,200,103,159,10,56,237,29,164,10]
+}"#;
+
+fn known_answer_inputs() -> (Vec<Scalar>, Vec<Scalar>) {
+    let a = (1_u64..=5).map(Scalar::from).collect();
+    let b = (6_u64..=10).map(Scalar::from).collect();
+    (a, b)
+}
+
+#[test]
+fn we_can_verify_the_known_answer_proof_on_the_host() {
+    let (a, b) = known_answer_inputs();
+    let mut g = vec![RistrettoPoint::default(); a.len()];
+    get_curve25519_generators(&mut g, 3);
+    let product = a.iter().zip(&b).map(|(a_i, b_i)| a_i * b_i).sum::<Scalar>();
+    let a_commit = a
+        .iter()
+        .zip(&g)
+        .map(|(a_i, g_i)| a_i * g_i)
+        .sum::<RistrettoPoint>();
+    let proof: InnerProductProof = serde_json::from_str(KNOWN_ANSWER_PROOF).unwrap();
+
+    let mut transcript = HostTranscript(Transcript::new(b"innerproductknownanswer"));
+    assert!(proof
+        .verify(&mut transcript, &a_commit, &product, &b, 3)
+        .is_ok());
+
+    let mut transcript = HostTranscript(Transcript::new(b"innerproductknownanswer"));
+    let host_proof = InnerProductProof::create(&mut transcript, &a, &b, 3);
+    assert_eq!(host_proof.l_vector, proof.l_vector);
+    assert_eq!(host_proof.r_vector, proof.r_vector);
+    assert_eq!(host_proof.ap_value, proof.ap_value);
+}
+
+#[cfg(feature = "blitzar-sys")]
+#[test]
+fn the_backend_reproduces_the_known_answer_proof() {
+    let (a, b) = known_answer_inputs();
+    let proof: InnerProductProof = serde_json::from_str(KNOWN_ANSWER_PROOF).unwrap();
+
+    let mut transcript = Transcript::new(b"innerproductknownanswer");
+    let backend_proof = InnerProductProof::create(&mut transcript, &a, &b, 3);
+    assert_eq!(backend_proof.l_vector, proof.l_vector);
+    assert_eq!(backend_proof.r_vector, proof.r_vector);
+    assert_eq!(backend_proof.ap_value, proof.ap_value);
+}
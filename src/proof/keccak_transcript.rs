@@ -0,0 +1,112 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::transcript::Transcript;
+use ark_ff::PrimeField;
+use curve25519_dalek::scalar::Scalar;
+use sha3::{Digest, Keccak256};
+
+/// Fiat-Shamir transcript based on Keccak-256.
+///
+/// Unlike the STROBE-based `merlin` transcript, every operation is a single
+/// Keccak-256 hash over tightly packed inputs, so the challenges can be
+/// reproduced by an EVM verifier with `keccak256(abi.encodePacked(...))`.
+///
+/// The transcript keeps a 32-byte `state` and uses the following layout, where
+/// `||` is concatenation and `u32_be` a 4-byte big-endian integer:
+///
+/// ```text
+/// new(label):
+///     state = keccak256(label)
+///
+/// append_message(label, message):
+///     state = keccak256(state || u32_be(len(label)) || label || u32_be(len(message)) || message)
+///
+/// append_u64(label, x):
+///     append_message(label, u64_be(x))
+///
+/// challenge_bytes(label, dest):
+///     state = keccak256(state || u32_be(len(label)) || label || u32_be(len(dest)))
+///     dest  = keccak256(state || u32_be(0)) || keccak256(state || u32_be(1)) || ...
+///             (truncated to len(dest) bytes)
+/// ```
+///
+/// Challenge scalars interpret 32 challenge bytes as a big-endian integer reduced
+/// modulo the field order, matching `uint256(bytes32) % order` in Solidity.
+#[derive(Clone, Debug)]
+pub struct KeccakTranscript {
+    state: [u8; 32],
+}
+
+impl KeccakTranscript {
+    /// Creates a new transcript with the given domain separation label.
+    pub fn new(label: &'static [u8]) -> Self {
+        Self {
+            state: Keccak256::digest(label).into(),
+        }
+    }
+
+    /// Returns the current 32-byte state of the transcript.
+    pub fn state(&self) -> [u8; 32] {
+        self.state
+    }
+
+    /// Derives a challenge in an arbitrary prime field.
+    ///
+    /// 32 challenge bytes are interpreted as a big-endian integer reduced modulo
+    /// the field order.
+    pub fn challenge_field_element<F: PrimeField>(&mut self, label: &'static [u8]) -> F {
+        let mut buf = [0_u8; 32];
+        self.challenge_bytes(label, &mut buf);
+        F::from_be_bytes_mod_order(&buf)
+    }
+
+    fn absorb_labeled(&mut self, label: &[u8], suffix: &[&[u8]]) {
+        let mut hasher = Keccak256::new();
+        hasher.update(self.state);
+        hasher.update((label.len() as u32).to_be_bytes());
+        hasher.update(label);
+        for bytes in suffix {
+            hasher.update(bytes);
+        }
+        self.state = hasher.finalize().into();
+    }
+}
+
+impl Transcript for KeccakTranscript {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.absorb_labeled(label, &[&(message.len() as u32).to_be_bytes(), message]);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.absorb_labeled(label, &[&(dest.len() as u32).to_be_bytes()]);
+        for (counter, chunk) in dest.chunks_mut(32).enumerate() {
+            let mut hasher = Keccak256::new();
+            hasher.update(self.state);
+            hasher.update((counter as u32).to_be_bytes());
+            let block = hasher.finalize();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+    }
+
+    fn append_u64(&mut self, label: &'static [u8], x: u64) {
+        self.append_message(label, &x.to_be_bytes());
+    }
+
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar {
+        let mut buf = [0_u8; 32];
+        self.challenge_bytes(label, &mut buf);
+        buf.reverse();
+        Scalar::from_bytes_mod_order(buf)
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use crate::compute::get_curve25519_generators;
+use ark_ff::PrimeField;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use rand_core::SeedableRng;
+use sha3::{Digest, Keccak256};
+
+fn keccak256(chunks: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    hasher.finalize().into()
+}
+
+#[test]
+fn the_keccak_transcript_follows_the_documented_layout() {
+    let mut transcript = KeccakTranscript::new(b"test");
+    let mut state = keccak256(&[b"test"]);
+    assert_eq!(transcript.state(), state);
+
+    transcript.append_message(b"msg", b"hello");
+    state = keccak256(&[
+        &state,
+        &3_u32.to_be_bytes(),
+        b"msg",
+        &5_u32.to_be_bytes(),
+        b"hello",
+    ]);
+    assert_eq!(transcript.state(), state);
+
+    transcript.append_u64(b"n", 7);
+    state = keccak256(&[
+        &state,
+        &1_u32.to_be_bytes(),
+        b"n",
+        &8_u32.to_be_bytes(),
+        &7_u64.to_be_bytes(),
+    ]);
+    assert_eq!(transcript.state(), state);
+
+    let mut challenge = [0_u8; 40];
+    transcript.challenge_bytes(b"x", &mut challenge);
+    state = keccak256(&[&state, &1_u32.to_be_bytes(), b"x", &40_u32.to_be_bytes()]);
+    assert_eq!(transcript.state(), state);
+    assert_eq!(challenge[..32], keccak256(&[&state, &0_u32.to_be_bytes()]));
+    assert_eq!(
+        challenge[32..],
+        keccak256(&[&state, &1_u32.to_be_bytes()])[..8]
+    );
+}
+
+#[test]
+fn keccak_transcript_challenges_are_big_endian_reductions() {
+    let mut t1 = KeccakTranscript::new(b"test");
+    let mut t2 = t1.clone();
+    let mut t3 = t1.clone();
+
+    let mut buf = [0_u8; 32];
+    t1.challenge_bytes(b"x", &mut buf);
+    assert_eq!(
+        t2.challenge_field_element::<ark_bn254::Fr>(b"x"),
+        ark_bn254::Fr::from_be_bytes_mod_order(&buf)
+    );
+
+    buf.reverse();
+    assert_eq!(t3.challenge_scalar(b"x"), Scalar::from_bytes_mod_order(buf));
+}
+
+#[test]
+fn keccak_transcript_challenges_depend_on_the_transcript() {
+    let mut t1 = KeccakTranscript::new(b"test");
+    let mut t2 = KeccakTranscript::new(b"test");
+    let mut t3 = KeccakTranscript::new(b"other");
+    t1.append_message(b"a", b"1");
+    t2.append_message(b"a", b"2");
+    t3.append_message(b"a", b"1");
+
+    let c1 = t1.challenge_scalar(b"x");
+    let c2 = t2.challenge_scalar(b"x");
+    let c3 = t3.challenge_scalar(b"x");
+    assert_ne!(c1, c2);
+    assert_ne!(c1, c3);
+
+    // consecutive challenges with the same label differ
+    assert_ne!(c1, t1.challenge_scalar(b"x"));
+}
+
+#[test]
+fn we_can_prove_and_verify_inner_products_with_a_keccak_transcript() {
+    let n = 5_u64;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(n);
+    let a: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+    let b: Vec<_> = (0..n).map(|_| Scalar::random(&mut rng)).collect();
+    let mut g = vec![RistrettoPoint::default(); n as usize];
+    get_curve25519_generators(&mut g, 0);
+    let product = a.iter().zip(&b).map(|(a_i, b_i)| a_i * b_i).sum::<Scalar>();
+    let a_commit = a
+        .iter()
+        .zip(&g)
+        .map(|(a_i, g_i)| a_i * g_i)
+        .sum::<RistrettoPoint>();
+
+    let mut transcript = KeccakTranscript::new(b"innerproducttest");
+    let proof = InnerProductProof::create(&mut transcript, &a, &b, 0);
+
+    let mut transcript = KeccakTranscript::new(b"innerproducttest");
+    assert!(proof
+        .verify(&mut transcript, &a_commit, &product, &b, 0)
+        .is_ok());
+
+    // proofs are bound to the transcript type
+    let mut transcript = merlin::Transcript::new(b"innerproducttest");
+    assert!(proof
+        .verify(&mut transcript, &a_commit, &product, &b, 0)
+        .is_err());
+}
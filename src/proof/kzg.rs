@@ -0,0 +1,339 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::error::ProofError;
+use crate::compute::{ElementP2, MsmHandle, SwCurveConfig, SwMsmHandle};
+use ark_ec::{
+    pairing::Pairing,
+    short_weierstrass::{Affine, Projective},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::Zero;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+/// A short Weierstrass `G1` curve that is part of a pairing-friendly engine.
+///
+/// Implemented for the `G1` configurations of `bls12-381` and `bn254`.
+pub trait KzgCurveConfig: SwCurveConfig + Clone {
+    /// The pairing engine whose `G1` group is given by this curve.
+    type Engine: Pairing<G1Affine = Affine<Self>, ScalarField = Self::ScalarField>;
+}
+
+impl KzgCurveConfig for ark_bls12_381::g1::Config {
+    type Engine = ark_bls12_381::Bls12_381;
+}
+
+impl KzgCurveConfig for ark_bn254::g1::Config {
+    type Engine = ark_bn254::Bn254;
+}
+
+type G2Affine<C> = <<C as KzgCurveConfig>::Engine as Pairing>::G2Affine;
+
+/// Powers-of-tau structured reference string (SRS) for KZG commitments.
+///
+/// The SRS holds the `G1` powers `[G, tau * G, tau^2 * G, ..., tau^d * G]`
+/// together with the `G2` elements `H` and `tau * H`. It supports committing
+/// to polynomials of degree at most `d`.
+#[derive(Clone, Debug)]
+pub struct KzgSrs<C: KzgCurveConfig> {
+    pub(crate) g1_powers: Vec<Affine<C>>,
+    pub(crate) g2: G2Affine<C>,
+    pub(crate) tau_g2: G2Affine<C>,
+}
+
+impl<C: KzgCurveConfig> KzgSrs<C> {
+    /// Creates an SRS from its `G1` powers and the `G2` elements `H` and `tau * H`.
+    pub fn new(g1_powers: Vec<Affine<C>>, g2: G2Affine<C>, tau_g2: G2Affine<C>) -> Self {
+        assert!(!g1_powers.is_empty());
+        Self {
+            g1_powers,
+            g2,
+            tau_g2,
+        }
+    }
+
+    /// Loads an SRS from a local file.
+    ///
+    /// The file holds, in order and in the compressed `ark-serialize` encoding,
+    /// the vector of `G1` powers (prefixed by its `u64` length), `H`, and `tau * H`.
+    /// Points are checked to be on the curve and in the prime order subgroup.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ProofError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let g1_powers = Vec::<Affine<C>>::deserialize_compressed(&mut reader)
+            .map_err(|_| ProofError::InvalidSrs)?;
+        let g2 = G2Affine::<C>::deserialize_compressed(&mut reader)
+            .map_err(|_| ProofError::InvalidSrs)?;
+        let tau_g2 = G2Affine::<C>::deserialize_compressed(&mut reader)
+            .map_err(|_| ProofError::InvalidSrs)?;
+        if g1_powers.is_empty() {
+            return Err(ProofError::InvalidSrs);
+        }
+        Ok(Self {
+            g1_powers,
+            g2,
+            tau_g2,
+        })
+    }
+
+    /// Writes the SRS to a local file in the format read by [KzgSrs::from_file].
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ProofError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.g1_powers
+            .serialize_compressed(&mut writer)
+            .and_then(|_| self.g2.serialize_compressed(&mut writer))
+            .and_then(|_| self.tau_g2.serialize_compressed(&mut writer))
+            .map_err(|_| ProofError::InvalidSrs)
+    }
+
+    /// Returns the largest polynomial degree supported by the SRS.
+    pub fn max_degree(&self) -> usize {
+        self.g1_powers.len() - 1
+    }
+
+    /// Returns the key used to verify commitment openings.
+    pub fn verifier_key(&self) -> KzgVerifierKey<C> {
+        KzgVerifierKey {
+            g1: self.g1_powers[0],
+            g2: self.g2,
+            tau_g2: self.tau_g2,
+        }
+    }
+}
+
+/// Opening proof for one or more polynomials committed with KZG.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KzgProof<C: KzgCurveConfig> {
+    /// Commitment to the quotient polynomial `(p(X) - p(z)) / (X - z)`.
+    pub quotient_commitment: Affine<C>,
+}
+
+/// Key to commit to polynomials and compute opening proofs.
+///
+/// The `G1` powers of the SRS are loaded into a [MsmHandle], so the
+/// precomputation is paid once per key.
+pub struct KzgProverKey<C: KzgCurveConfig> {
+    handle: MsmHandle<ElementP2<C>>,
+    num_powers: usize,
+}
+
+impl<C: KzgCurveConfig> KzgProverKey<C> {
+    /// Creates a prover key from an SRS.
+    pub fn new(srs: &KzgSrs<C>) -> Self {
+        Self {
+            handle: MsmHandle::new_with_affine(&srs.g1_powers),
+            num_powers: srs.g1_powers.len(),
+        }
+    }
+
+    /// Returns the largest polynomial degree supported by the key.
+    pub fn max_degree(&self) -> usize {
+        self.num_powers - 1
+    }
+
+    /// Commits to polynomials given by their coefficients, lowest degree first.
+    ///
+    /// All commitments are computed with a single multi-output MSM.
+    pub fn commit(&self, polynomials: &[&[C::ScalarField]]) -> Result<Vec<Affine<C>>, ProofError> {
+        let n = polynomials.iter().map(|p| p.len()).max().unwrap_or(0);
+        if n > self.num_powers {
+            return Err(ProofError::DegreeTooLarge);
+        }
+        let mut res = vec![Affine::<C>::zero(); polynomials.len()];
+        if n == 0 {
+            return Ok(res);
+        }
+
+        // lay out the coefficients as a column-major matrix with one row per power of tau
+        let element_num_bytes = scalar_num_bytes::<C::ScalarField>();
+        let mut scalars = vec![0_u8; element_num_bytes * polynomials.len() * n];
+        for (output_index, polynomial) in polynomials.iter().enumerate() {
+            for (power_index, coefficient) in polynomial.iter().enumerate() {
+                let offset = (power_index * polynomials.len() + output_index) * element_num_bytes;
+                let bytes = coefficient.into_bigint().to_bytes_le();
+                scalars[offset..offset + element_num_bytes]
+                    .copy_from_slice(&bytes[..element_num_bytes]);
+            }
+        }
+        self.handle
+            .affine_msm(&mut res, element_num_bytes as u32, &scalars)?;
+        Ok(res)
+    }
+
+    /// Opens a polynomial at `point`.
+    ///
+    /// Returns the evaluation `p(point)` together with the proof.
+    pub fn open(
+        &self,
+        polynomial: &[C::ScalarField],
+        point: &C::ScalarField,
+    ) -> Result<(C::ScalarField, KzgProof<C>), ProofError> {
+        let (value, quotient) = divide_by_linear(polynomial, point);
+        let quotient_commitment = self.commit(&[&quotient])?[0];
+        Ok((
+            value,
+            KzgProof {
+                quotient_commitment,
+            },
+        ))
+    }
+
+    /// Opens several polynomials at the same `point` with a single proof.
+    ///
+    /// The polynomials are combined as `sum_i challenge^i * p_i` before opening,
+    /// so `challenge` must be sampled by the verifier (or derived from a transcript
+    /// that binds the commitments) after the commitments are fixed.
+    ///
+    /// Returns the evaluations `p_i(point)` together with the proof.
+    pub fn batch_open(
+        &self,
+        polynomials: &[&[C::ScalarField]],
+        point: &C::ScalarField,
+        challenge: &C::ScalarField,
+    ) -> Result<(Vec<C::ScalarField>, KzgProof<C>), ProofError> {
+        let values = polynomials
+            .iter()
+            .map(|p| evaluate(p, point))
+            .collect::<Vec<_>>();
+
+        let n = polynomials.iter().map(|p| p.len()).max().unwrap_or(0);
+        let mut combined = vec![C::ScalarField::zero(); n];
+        let mut power = C::ScalarField::from(1_u64);
+        for polynomial in polynomials {
+            for (c, coefficient) in combined.iter_mut().zip(polynomial.iter()) {
+                *c += power * coefficient;
+            }
+            power *= challenge;
+        }
+
+        let (_, proof) = self.open(&combined, point)?;
+        Ok((values, proof))
+    }
+}
+
+/// Key to verify KZG opening proofs.
+#[derive(Clone, Debug)]
+pub struct KzgVerifierKey<C: KzgCurveConfig> {
+    pub(crate) g1: Affine<C>,
+    pub(crate) g2: G2Affine<C>,
+    pub(crate) tau_g2: G2Affine<C>,
+}
+
+impl<C: KzgCurveConfig> KzgVerifierKey<C> {
+    /// Verifies that the polynomial committed to by `commitment` evaluates to
+    /// `value` at `point`.
+    ///
+    /// The check is the pairing equation
+    ///
+    /// ```text
+    /// e(commitment - value * G, H) == e(proof, tau * H - point * H)
+    /// ```
+    pub fn verify(
+        &self,
+        commitment: &Affine<C>,
+        point: &C::ScalarField,
+        value: &C::ScalarField,
+        proof: &KzgProof<C>,
+    ) -> Result<(), ProofError> {
+        let lhs = (commitment.into_group() - self.g1 * value).into_affine();
+        let rhs = (self.tau_g2.into_group() - self.g2 * point).into_affine();
+        if C::Engine::pairing(lhs, self.g2) == C::Engine::pairing(proof.quotient_commitment, rhs) {
+            Ok(())
+        } else {
+            Err(ProofError::VerificationError)
+        }
+    }
+
+    /// Verifies a proof computed by [KzgProverKey::batch_open].
+    ///
+    /// `challenge` must be the same value used to create the proof.
+    pub fn batch_verify(
+        &self,
+        commitments: &[Affine<C>],
+        point: &C::ScalarField,
+        values: &[C::ScalarField],
+        proof: &KzgProof<C>,
+        challenge: &C::ScalarField,
+    ) -> Result<(), ProofError> {
+        if commitments.len() != values.len() {
+            return Err(ProofError::VerificationError);
+        }
+        let mut commitment = Projective::<C>::zero();
+        let mut value = C::ScalarField::zero();
+        let mut power = C::ScalarField::from(1_u64);
+        for (c, v) in commitments.iter().zip(values) {
+            commitment += *c * power;
+            value += power * v;
+            power *= challenge;
+        }
+        self.verify(&commitment.into_affine(), point, &value, proof)
+    }
+}
+
+/// Number of bytes needed to represent any element of the field.
+fn scalar_num_bytes<F: PrimeField>() -> usize {
+    (F::MODULUS_BIT_SIZE as usize).div_ceil(8)
+}
+
+/// Evaluates the polynomial at `point` using Horner's method.
+fn evaluate<F: PrimeField>(polynomial: &[F], point: &F) -> F {
+    polynomial
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, coefficient| acc * point + coefficient)
+}
+
+/// Divides the polynomial by `X - point`.
+///
+/// Returns the remainder `p(point)` and the coefficients of the quotient.
+fn divide_by_linear<F: PrimeField>(polynomial: &[F], point: &F) -> (F, Vec<F>) {
+    if polynomial.is_empty() {
+        return (F::zero(), Vec::new());
+    }
+    let mut quotient = vec![F::zero(); polynomial.len() - 1];
+    let mut remainder = polynomial[polynomial.len() - 1];
+    for i in (0..quotient.len()).rev() {
+        quotient[i] = remainder;
+        remainder = polynomial[i] + remainder * point;
+    }
+    (remainder, quotient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::Fr;
+
+    #[test]
+    fn we_can_divide_a_polynomial_by_a_linear_factor() {
+        // (x^2 + 3x + 2) / (x - 1) = x + 4 with remainder 6
+        let p = [Fr::from(2_u64), Fr::from(3_u64), Fr::from(1_u64)];
+        let (r, q) = divide_by_linear(&p, &Fr::from(1_u64));
+        assert_eq!(r, Fr::from(6_u64));
+        assert_eq!(q, vec![Fr::from(4_u64), Fr::from(1_u64)]);
+        assert_eq!(r, evaluate(&p, &Fr::from(1_u64)));
+
+        // we handle constant and empty polynomials
+        let (r, q) = divide_by_linear(&[Fr::from(5_u64)], &Fr::from(7_u64));
+        assert_eq!(r, Fr::from(5_u64));
+        assert!(q.is_empty());
+        let (r, q) = divide_by_linear::<Fr>(&[], &Fr::from(7_u64));
+        assert_eq!(r, Fr::zero());
+        assert!(q.is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+use ark_ec::{pairing::Pairing, short_weierstrass::Affine, AffineRepr, CurveGroup};
+use ark_ff::{Field, PrimeField};
+use ark_std::UniformRand;
+
+fn insecure_srs<C: KzgCurveConfig>(max_degree: usize) -> KzgSrs<C> {
+    let mut rng = ark_std::test_rng();
+    let tau = C::ScalarField::rand(&mut rng);
+    let g1 = Affine::<C>::generator();
+    let g1_powers = (0..=max_degree)
+        .map(|i| (g1 * tau.pow([i as u64])).into_affine())
+        .collect();
+    let g2 = <C::Engine as Pairing>::G2Affine::generator();
+    KzgSrs::new(g1_powers, g2, (g2 * tau).into_affine())
+}
+
+fn random_polynomial<F: PrimeField>(len: usize, rng: &mut impl ark_std::rand::Rng) -> Vec<F> {
+    (0..len).map(|_| F::rand(rng)).collect()
+}
+
+fn we_can_open_and_verify_polynomials<C: KzgCurveConfig>() {
+    let mut rng = ark_std::test_rng();
+    let srs = insecure_srs::<C>(8);
+    let prover_key = KzgProverKey::new(&srs);
+    let verifier_key = srs.verifier_key();
+
+    let p = random_polynomial::<C::ScalarField>(9, &mut rng);
+    let z = C::ScalarField::rand(&mut rng);
+    let commitment = prover_key.commit(&[&p]).unwrap()[0];
+    let (value, proof) = prover_key.open(&p, &z).unwrap();
+
+    // we can verify a valid opening
+    assert!(verifier_key.verify(&commitment, &z, &value, &proof).is_ok());
+
+    // we cannot verify an opening to a different value
+    let bad_value = value + C::ScalarField::from(1_u64);
+    assert!(verifier_key
+        .verify(&commitment, &z, &bad_value, &proof)
+        .is_err());
+
+    // we cannot verify an opening at a different point
+    let bad_z = z + C::ScalarField::from(1_u64);
+    assert!(verifier_key
+        .verify(&commitment, &bad_z, &value, &proof)
+        .is_err());
+}
+
+fn we_can_batch_open_and_verify_polynomials<C: KzgCurveConfig>() {
+    let mut rng = ark_std::test_rng();
+    let srs = insecure_srs::<C>(8);
+    let prover_key = KzgProverKey::new(&srs);
+    let verifier_key = srs.verifier_key();
+
+    let p1 = random_polynomial::<C::ScalarField>(9, &mut rng);
+    let p2 = random_polynomial::<C::ScalarField>(3, &mut rng);
+    let p3 = random_polynomial::<C::ScalarField>(1, &mut rng);
+    let polynomials: [&[C::ScalarField]; 3] = [&p1, &p2, &p3];
+    let z = C::ScalarField::rand(&mut rng);
+    let challenge = C::ScalarField::rand(&mut rng);
+
+    let commitments = prover_key.commit(&polynomials).unwrap();
+    let (values, proof) = prover_key.batch_open(&polynomials, &z, &challenge).unwrap();
+
+    // we can verify a valid batch opening
+    assert!(verifier_key
+        .batch_verify(&commitments, &z, &values, &proof, &challenge)
+        .is_ok());
+
+    // the batched commitments agree with committing one polynomial at a time
+    for (polynomial, commitment) in polynomials.iter().zip(&commitments) {
+        assert_eq!(prover_key.commit(&[polynomial]).unwrap()[0], *commitment);
+    }
+
+    // we cannot verify a batch opening with a tampered value
+    let mut bad_values = values.clone();
+    bad_values[1] += C::ScalarField::from(1_u64);
+    assert!(verifier_key
+        .batch_verify(&commitments, &z, &bad_values, &proof, &challenge)
+        .is_err());
+
+    // we cannot verify a batch opening with a different challenge
+    let bad_challenge = challenge + C::ScalarField::from(1_u64);
+    assert!(verifier_key
+        .batch_verify(&commitments, &z, &values, &proof, &bad_challenge)
+        .is_err());
+}
+
+#[test]
+fn we_can_open_and_verify_bls12_381_polynomials() {
+    we_can_open_and_verify_polynomials::<ark_bls12_381::g1::Config>();
+}
+
+#[test]
+fn we_can_open_and_verify_bn254_polynomials() {
+    we_can_open_and_verify_polynomials::<ark_bn254::g1::Config>();
+}
+
+#[test]
+fn we_can_batch_open_and_verify_bls12_381_polynomials() {
+    we_can_batch_open_and_verify_polynomials::<ark_bls12_381::g1::Config>();
+}
+
+#[test]
+fn we_can_batch_open_and_verify_bn254_polynomials() {
+    we_can_batch_open_and_verify_polynomials::<ark_bn254::g1::Config>();
+}
+
+#[test]
+fn we_cannot_commit_to_a_polynomial_larger_than_the_srs() {
+    let srs = insecure_srs::<ark_bn254::g1::Config>(2);
+    let prover_key = KzgProverKey::new(&srs);
+    let p = vec![ark_bn254::Fr::from(1_u64); 4];
+    assert!(matches!(
+        prover_key.commit(&[&p]),
+        Err(ProofError::DegreeTooLarge)
+    ));
+}
+
+#[test]
+fn we_can_write_and_load_an_srs_file() {
+    let srs = insecure_srs::<ark_bls12_381::g1::Config>(4);
+    let path = std::env::temp_dir().join("blitzar_kzg_srs_roundtrip_test.bin");
+    srs.write_to_file(&path).unwrap();
+    let loaded = KzgSrs::<ark_bls12_381::g1::Config>::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.max_degree(), 4);
+    let vk = srs.verifier_key();
+    let loaded_vk = loaded.verifier_key();
+    assert_eq!(vk.g1, loaded_vk.g1);
+    assert_eq!(vk.g2, loaded_vk.g2);
+    assert_eq!(vk.tau_g2, loaded_vk.tau_g2);
+}
+
+#[test]
+fn we_cannot_load_a_truncated_srs_file() {
+    let srs = insecure_srs::<ark_bn254::g1::Config>(4);
+    let path = std::env::temp_dir().join("blitzar_kzg_srs_truncated_test.bin");
+    srs.write_to_file(&path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+    let res = KzgSrs::<ark_bn254::g1::Config>::from_file(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(res, Err(ProofError::InvalidSrs)));
+
+    // a missing file is reported as an I/O error
+    assert!(matches!(
+        KzgSrs::<ark_bn254::g1::Config>::from_file(&path),
+        Err(ProofError::Io(_))
+    ));
+}
@@ -17,8 +17,27 @@
 mod error;
 pub use error::ProofError;
 
+mod transcript;
+pub use transcript::Transcript;
+
+mod keccak_transcript;
+pub use keccak_transcript::KeccakTranscript;
+
+#[cfg(test)]
+mod keccak_transcript_tests;
+
 mod inner_product;
 pub use inner_product::InnerProductProof;
 
+mod inner_product_host;
+
 #[cfg(test)]
 mod inner_product_tests;
+
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+mod kzg;
+#[cfg(any(feature = "blitzar-sys", feature = "reference"))]
+pub use kzg::{KzgCurveConfig, KzgProof, KzgProverKey, KzgSrs, KzgVerifierKey};
+
+#[cfg(all(test, any(feature = "blitzar-sys", feature = "reference")))]
+mod kzg_tests;
@@ -0,0 +1,70 @@
+// Copyright 2023-present Space and Time Labs, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use curve25519_dalek::scalar::Scalar;
+
+/// Fiat-Shamir transcript used by the proof primitives.
+///
+/// The proof primitives only absorb labeled messages and squeeze labeled
+/// challenge bytes, so any hash-based transcript can be plugged in.
+///
+/// The `merlin` transcript is the default implementation. Proofs created with it
+/// are computed entirely by the backend, while proofs created with any other
+/// transcript are computed on the host with the same protocol.
+pub trait Transcript {
+    /// Appends a labeled message to the transcript.
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]);
+
+    /// Fills `dest` with challenge bytes that depend on the whole transcript.
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]);
+
+    /// Appends a labeled `u64`, encoded as 8 little-endian bytes.
+    fn append_u64(&mut self, label: &'static [u8], x: u64) {
+        self.append_message(label, &x.to_le_bytes());
+    }
+
+    /// Derives a curve25519 scalar challenge.
+    ///
+    /// 32 challenge bytes are drawn and reduced modulo the group order.
+    fn challenge_scalar(&mut self, label: &'static [u8]) -> Scalar {
+        let mut buf = [0_u8; 32];
+        self.challenge_bytes(label, &mut buf);
+        Scalar::from_bytes_mod_order(buf)
+    }
+
+    /// Returns the underlying `merlin` transcript, if any.
+    ///
+    /// The backend can only drive `merlin` transcripts directly. Implementations
+    /// other than [merlin::Transcript] should keep the default.
+    fn as_merlin_transcript(&mut self) -> Option<&mut merlin::Transcript> {
+        None
+    }
+}
+
+impl Transcript for merlin::Transcript {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        merlin::Transcript::append_message(self, label, message);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        merlin::Transcript::challenge_bytes(self, label, dest);
+    }
+
+    fn append_u64(&mut self, label: &'static [u8], x: u64) {
+        merlin::Transcript::append_u64(self, label, x);
+    }
+
+    fn as_merlin_transcript(&mut self) -> Option<&mut merlin::Transcript> {
+        Some(self)
+    }
+}
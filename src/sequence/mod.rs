@@ -14,6 +14,7 @@
 
 //! data and scalar field elements for data table
 
+#[cfg(feature = "blitzar-sys")]
 use blitzar_sys::sxt_sequence_descriptor;
 
 /// Stores the slice view of a contiguous column data table.
@@ -60,6 +61,10 @@ pub struct Sequence<'a> {
 
     /// Represents whether the data slice should be interpreted
     /// as a sequence of signed or unsigned values.
+    #[cfg_attr(
+        not(any(feature = "blitzar-sys", feature = "reference")),
+        allow(dead_code)
+    )]
     is_signed: bool,
 }
 
@@ -74,6 +79,33 @@ impl<'a> Sequence<'a> {
         self.len() == 0
     }
 
+    /// Returns the number of bytes of each element.
+    #[cfg_attr(
+        not(any(feature = "blitzar-sys", feature = "reference")),
+        allow(dead_code)
+    )]
+    pub(crate) fn element_size(&self) -> usize {
+        self.element_size
+    }
+
+    /// Returns whether the elements are interpreted as signed values.
+    #[cfg_attr(
+        not(any(feature = "blitzar-sys", feature = "reference")),
+        allow(dead_code)
+    )]
+    pub(crate) fn is_signed(&self) -> bool {
+        self.is_signed
+    }
+
+    /// Returns the little-endian bytes of the elements.
+    #[cfg_attr(
+        not(any(feature = "blitzar-sys", feature = "reference")),
+        allow(dead_code)
+    )]
+    pub(crate) fn as_bytes(&self) -> &'a [u8] {
+        self.data_slice
+    }
+
     /// Converts a slice of any type to a Sequence by calling `from_raw_parts` on it.
     /// The `is_signed` parameter is used to determine whether the data is interpreted as a signed value or not.
     /// Several types are also supported via the `From` trait, which is preferred over this method.
@@ -99,6 +131,7 @@ impl<'a> Sequence<'a> {
     }
 }
 
+#[cfg(feature = "blitzar-sys")]
 impl From<&Sequence<'_>> for sxt_sequence_descriptor {
     fn from(other: &Sequence<'_>) -> Self {
         sxt_sequence_descriptor {